@@ -29,6 +29,10 @@ pub struct ClangParsedResult {
     pub source_include_headers: RustBtreeMapOfStrSet,
     // Box::into_raw(Box::new(BTreeMap::<String, BTreeSet::<String>>::new()))
     pub header_include_by_sources: RustBtreeMapOfStrSet,
+    // Box::into_raw(Box::new(BTreeMap::<String, BTreeSet::<String>>::new()))
+    pub third_party_include_by_sources: RustBtreeMapOfStrSet,
+    // Box::into_raw(Box::new(BTreeMap::<String, BTreeSet::<String>>::new()))
+    pub third_party_angled_include_by_sources: RustBtreeMapOfStrSet,
 }
 
 extern "C" {
@@ -77,6 +81,11 @@ pub enum AstCErrorCode {
     AstCErrorSymbolClangGetTypeSpellingNotFound = 1020,
     AstCErrorSymbolClangGetCursorSemanticParentNotFound = 1021,
     AstCErrorSymbolClangCursorIsNullNotFound = 1022,
+    AstCErrorSymbolClangGetCursorExtentNotFound = 1023,
+    AstCErrorSymbolClangGetRangeStartNotFound = 1024,
+    AstCErrorSymbolClangGetRangeEndNotFound = 1025,
+    AstCErrorSymbolClangCursorGetTranslationUnitNotFound = 1026,
+    AstCErrorSymbolClangGetFileContentsNotFound = 1027,
 
     // call function errors
     AstCErrorSymbolClangCreateIndexCall = 5001,
@@ -101,6 +110,11 @@ pub enum AstCErrorCode {
     AstCErrorSymbolClangGetTypeSpellingCall = 5020,
     AstCErrorSymbolClangGetCursorSemanticParentCall = 5021,
     AstCErrorSymbolClangCursorIsNullCall = 5022,
+    AstCErrorSymbolClangGetCursorExtentCall = 5023,
+    AstCErrorSymbolClangGetRangeStartCall = 5024,
+    AstCErrorSymbolClangGetRangeEndCall = 5025,
+    AstCErrorSymbolClangCursorGetTranslationUnitCall = 5026,
+    AstCErrorSymbolClangGetFileContentsCall = 5027,
 
     // unknown error
     AstCErrorUnknown = 65535,
@@ -143,6 +157,11 @@ impl From<i32> for AstCErrorCode {
             1020 => AstCErrorCode::AstCErrorSymbolClangGetTypeSpellingNotFound,
             1021 => AstCErrorCode::AstCErrorSymbolClangGetCursorSemanticParentNotFound,
             1022 => AstCErrorCode::AstCErrorSymbolClangCursorIsNullNotFound,
+            1023 => AstCErrorCode::AstCErrorSymbolClangGetCursorExtentNotFound,
+            1024 => AstCErrorCode::AstCErrorSymbolClangGetRangeStartNotFound,
+            1025 => AstCErrorCode::AstCErrorSymbolClangGetRangeEndNotFound,
+            1026 => AstCErrorCode::AstCErrorSymbolClangCursorGetTranslationUnitNotFound,
+            1027 => AstCErrorCode::AstCErrorSymbolClangGetFileContentsNotFound,
 
             // call function errors
             5001 => AstCErrorCode::AstCErrorSymbolClangCreateIndexCall,
@@ -167,6 +186,11 @@ impl From<i32> for AstCErrorCode {
             5020 => AstCErrorCode::AstCErrorSymbolClangGetTypeSpellingCall,
             5021 => AstCErrorCode::AstCErrorSymbolClangGetCursorSemanticParentCall,
             5022 => AstCErrorCode::AstCErrorSymbolClangCursorIsNullCall,
+            5023 => AstCErrorCode::AstCErrorSymbolClangGetCursorExtentCall,
+            5024 => AstCErrorCode::AstCErrorSymbolClangGetRangeStartCall,
+            5025 => AstCErrorCode::AstCErrorSymbolClangGetRangeEndCall,
+            5026 => AstCErrorCode::AstCErrorSymbolClangCursorGetTranslationUnitCall,
+            5027 => AstCErrorCode::AstCErrorSymbolClangGetFileContentsCall,
 
             // unknown error
             _ => AstCErrorCode::AstCErrorUnknown,
@@ -13,6 +13,8 @@ pub struct SourceMappings {
     pub source_symbols: BTreeMap<String, BTreeSet<String>>,
     pub source_include_headers: BTreeMap<String, BTreeSet<String>>,
     pub header_include_by_sources: BTreeMap<String, BTreeSet<String>>,
+    pub third_party_include_by_sources: BTreeMap<String, BTreeSet<String>>,
+    pub third_party_angled_include_by_sources: BTreeMap<String, BTreeSet<String>>,
 }
 
 impl SourceMappings {
@@ -84,7 +86,7 @@ impl SourceMappings {
             }
         }
 
-        self.append_implemented_sources(necessaries);
+        self.append_implemented_sources(entry_point_source, necessaries);
     }
 
     fn collect_symbols_and_sources(
@@ -116,6 +118,17 @@ impl SourceMappings {
                 result.header_include_by_sources as *mut BTreeMap<String, BTreeSet<String>>,
             )
         };
+        let third_party_include_by_sources = unsafe {
+            Box::from_raw(
+                result.third_party_include_by_sources as *mut BTreeMap<String, BTreeSet<String>>,
+            )
+        };
+        let third_party_angled_include_by_sources = unsafe {
+            Box::from_raw(
+                result.third_party_angled_include_by_sources
+                    as *mut BTreeMap<String, BTreeSet<String>>,
+            )
+        };
 
         // extend parsed results
         self.parsed_files.extend(current_parsed_files.into_iter());
@@ -137,12 +150,25 @@ impl SourceMappings {
                 .or_insert_with(BTreeSet::new)
                 .extend(sources);
         }
+        for (include, sources) in third_party_include_by_sources.into_iter() {
+            self.third_party_include_by_sources
+                .entry(include)
+                .or_insert_with(BTreeSet::new)
+                .extend(sources);
+        }
+        for (include, sources) in third_party_angled_include_by_sources.into_iter() {
+            self.third_party_angled_include_by_sources
+                .entry(include)
+                .or_insert_with(BTreeSet::new)
+                .extend(sources);
+        }
 
         return header_include_by_sources;
     }
 
     fn append_implemented_sources(
         &mut self,
+        entry_point_source: &String,
         mut necessaries: Box<BTreeMap<String, BTreeSet<String>>>,
     ) {
         let mut parsed_files = HashSet::new();
@@ -199,6 +225,7 @@ impl SourceMappings {
 
         // flatten necessary sources
         let mut flatten_necessaries = HashSet::new();
+        flatten_necessaries.insert(entry_point_source);
         for (header, sources) in necessaries.iter() {
             flatten_necessaries.insert(header);
             flatten_necessaries.extend(sources);
@@ -206,6 +233,17 @@ impl SourceMappings {
         // clean unnecessary source and symbols
         self.source_symbols
             .retain(|source, _| flatten_necessaries.contains(source));
+        // keep third-party inclusions of necessary sources only
+        for sources in self.third_party_include_by_sources.values_mut() {
+            sources.retain(|source| flatten_necessaries.contains(source));
+        }
+        self.third_party_include_by_sources
+            .retain(|_, sources| !sources.is_empty());
+        for sources in self.third_party_angled_include_by_sources.values_mut() {
+            sources.retain(|source| flatten_necessaries.contains(source));
+        }
+        self.third_party_angled_include_by_sources
+            .retain(|_, sources| !sources.is_empty());
 
         self.parsed_files.clear();
         self.source_include_headers.clear();
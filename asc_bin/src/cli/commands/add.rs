@@ -2,7 +2,7 @@ use clap::Args;
 
 use crate::dependency;
 
-#[derive(Args, Debug, Clone)]
+#[derive(Args, Debug, Default, Clone)]
 /// add dependency to package or workspace memeber's asc.toml
 pub struct AddArgs {
     /// dependency name
@@ -12,11 +12,27 @@ pub struct AddArgs {
     #[clap(long)]
     pub package: Option<String>,
 
-    /// dependency version (default latest)
+    /// dependency version or requirement (default latest, --version=^1.14, --version=">=3.0,<4")
     #[clap(long, default_value = "")]
     pub version: String,
 
-    /// for cmake find_package (--find-package=a --find-package=b@!windows)
+    /// vcpkg registry name of port, resolved by registry priority and packages if omitted (--registry=vcpkg)
+    #[clap(long, default_value = "")]
+    pub registry: String,
+
+    /// workspace member or local source dir relative to the package dir (--path=../b)
+    #[clap(long, default_value = "")]
+    pub path: String,
+
+    /// git repository url of source dependency (--git=https://github.com/a/b.git)
+    #[clap(long, default_value = "")]
+    pub git: String,
+
+    /// git branch, tag or commit of source dependency, required by --git
+    #[clap(long, default_value = "")]
+    pub rev: String,
+
+    /// for cmake find_package (--find-package=a --find-package=b@!windows), discovered from port usage if omitted
     #[clap(long)]
     pub find_package: Vec<String>,
 
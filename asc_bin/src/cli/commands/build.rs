@@ -1,6 +1,6 @@
 use clap::Args;
 
-use super::{scan::ScanOptions, ConfigType};
+use super::{scan::ScanOptions, select_profile, select_triplets};
 use crate::{cmake, config, config::relative_paths, util};
 
 #[derive(Args, Debug, Default, Clone)]
@@ -13,6 +13,30 @@ pub struct BuildArgs {
     /// release mode (default false)
     #[clap(long, default_value_t = false)]
     release: bool,
+
+    /// build profile (debug, release, relwithdebinfo, minsizerel or [profile.<name>] in asc.toml)
+    #[clap(long)]
+    pub profile: Option<String>,
+
+    /// build with sanitizers (address, undefined, thread, memory, leak)
+    #[clap(long, value_delimiter = ',')]
+    pub sanitize: Vec<String>,
+
+    /// build examples too (default false)
+    #[clap(long, default_value_t = false)]
+    pub examples: bool,
+
+    /// vcpkg target triplet (default [vcpkg] target_triplet in asc.toml or host), built in target/<triplet>
+    #[clap(long)]
+    pub target_triplet: Option<String>,
+
+    /// vcpkg host triplet (default [vcpkg] host_triplet in asc.toml or host)
+    #[clap(long)]
+    pub host_triplet: Option<String>,
+
+    /// cmake toolchain file chainloaded by vcpkg (default [vcpkg] toolchain_file in asc.toml)
+    #[clap(long)]
+    pub toolchain_file: Option<String>,
 }
 
 impl BuildArgs {
@@ -27,16 +51,49 @@ impl BuildArgs {
             return false;
         }
 
-        let mut options = ScanOptions {
-            target_dir: relative_paths::ASC_TARGET_DIR_NAME.to_string(),
-            cmake_config: ConfigType::from(self.release).as_ref().to_string(),
-            ..Default::default()
+        let mut options = match select_profile(self.release, &self.profile, &self.sanitize) {
+            None => return false,
+            Some((options, _)) => options,
         };
+        if !select_triplets(
+            &mut options,
+            &self.target_triplet,
+            &self.host_triplet,
+            &self.toolchain_file,
+        ) {
+            return false;
+        }
+
+        // configure profile or triplet build directory
+        if options.target_dir != relative_paths::ASC_TARGET_DIR_NAME {
+            let cwd = util::fs::get_cwd();
+            cmake::project::gen(&ScanOptions {
+                project_dir: format!("{cwd}/{}", relative_paths::ASC_PROJECT_DIR_NAME),
+                target_dir: format!("{cwd}/{}", options.target_dir),
+                cmake_config: options.cmake_config.clone(),
+                sanitizers: options.sanitizers.clone(),
+                target_triplet: options.target_triplet.clone(),
+                host_triplet: options.host_triplet.clone(),
+                toolchain_file: options.toolchain_file.clone(),
+                ..Default::default()
+            });
+        }
+
         if let Some(t) = &self.target {
             options.project = t.clone();
         }
         cmake::build::exec(&options);
 
+        // examples were excluded from all
+        if self.examples {
+            if let Some(project_conf) = config::project::ProjectConfig::read_project_conf() {
+                for example in project_conf.flatten_examples() {
+                    options.project = example.name;
+                    cmake::build::exec(&options);
+                }
+            }
+        }
+
         return true;
     }
 }
@@ -0,0 +1,45 @@
+use clap::Args;
+
+use crate::vcpkg;
+
+use super::CacheAction;
+
+#[derive(Args, Debug, Clone)]
+/// report, prune, export and import vcpkg binary cache
+pub struct CacheArgs {
+    /// size/prune/export/import
+    pub action: CacheAction,
+
+    /// prune entries which were not modified in the last N days
+    #[clap(long)]
+    pub days: Option<u64>,
+
+    /// prune entries which were not referenced by lock files, export referenced entries only if lock files were given
+    #[clap(long, default_value_t = false)]
+    pub unreferenced: bool,
+
+    /// asc.lock paths (default asc.lock in current dir)
+    #[clap(long)]
+    pub lock: Vec<String>,
+
+    /// show entries to prune without removing them
+    #[clap(long, default_value_t = false)]
+    pub dry_run: bool,
+
+    /// export/import archive path
+    #[clap(long)]
+    pub path: Option<String>,
+}
+
+impl CacheArgs {
+    pub fn exec(&self) -> bool {
+        tracing::info!(message = "cache", action = self.action.as_ref());
+
+        match self.action {
+            CacheAction::Size => vcpkg::cache::size(self),
+            CacheAction::Prune => vcpkg::cache::prune(self),
+            CacheAction::Export => vcpkg::cache::export(self),
+            CacheAction::Import => vcpkg::cache::import(self),
+        }
+    }
+}
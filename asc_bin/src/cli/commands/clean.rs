@@ -2,6 +2,7 @@ use clap::Args;
 
 use tracing;
 
+use super::{scan::ScanOptions, select_triplets};
 use crate::{
     config::{self, project::ProjectConfig, relative_paths},
     errors::ErrorTag,
@@ -10,10 +11,29 @@ use crate::{
 
 #[derive(Args, Debug, Clone)]
 /// clean .asc and target dir
-pub struct CleanArgs {}
+pub struct CleanArgs {
+    /// clean build of vcpkg target triplet only (target/<triplet>)
+    #[clap(long)]
+    target_triplet: Option<String>,
+
+    /// clean build of vcpkg host triplet only
+    #[clap(long)]
+    host_triplet: Option<String>,
+
+    /// clean build of chainloaded cmake toolchain file only
+    #[clap(long)]
+    toolchain_file: Option<String>,
+}
 
 impl CleanArgs {
     pub fn exec(&self) -> bool {
+        if self.target_triplet.is_some()
+            || self.host_triplet.is_some()
+            || self.toolchain_file.is_some()
+        {
+            return self.clean_triplet();
+        }
+
         match config::project::ProjectConfig::read_project_conf() {
             None => {
                 tracing::error!(error_tag = ErrorTag::InvalidProjectError.as_ref(),);
@@ -29,8 +49,37 @@ impl CleanArgs {
         }
     }
 
+    // profiles and sanitizer builds of the triplet were in the same dir
+    fn clean_triplet(&self) -> bool {
+        let mut options = ScanOptions {
+            target_dir: relative_paths::ASC_TARGET_DIR_NAME.to_string(),
+            ..Default::default()
+        };
+        if !select_triplets(
+            &mut options,
+            &self.target_triplet,
+            &self.host_triplet,
+            &self.toolchain_file,
+        ) {
+            return false;
+        }
+        if options.target_dir == relative_paths::ASC_TARGET_DIR_NAME {
+            tracing::error!(
+                error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                message = "host build was cleaned by asc clean without triplet options"
+            );
+            return false;
+        }
+
+        tracing::info!(message = "clean triplet", path = options.target_dir);
+        if util::fs::is_dir_exists(&options.target_dir) {
+            return util::fs::remove_dirs(&options.target_dir);
+        }
+        return true;
+    }
+
     fn clean_package(&self, project_conf: &ProjectConfig) -> bool {
-        let mut has_error = false;
+        let mut result = true;
 
         let cwd = util::fs::get_cwd();
         for bin in &project_conf.bins {
@@ -42,10 +91,10 @@ impl CleanArgs {
             tracing::info!(message = "clean bin", name = bin.name);
 
             // cmake
-            has_error &= relative_paths::clean_cmake_files(&bin.name);
+            result &= relative_paths::clean_cmake_files(&bin.name);
 
             // graph
-            has_error &= relative_paths::clean_graph_files();
+            result &= relative_paths::clean_graph_files();
 
             util::fs::set_cwd("..");
             util::fs::remove_dir(&bin.name);
@@ -61,26 +110,69 @@ impl CleanArgs {
             tracing::info!(message = "clean lib", name = lib.name);
 
             // cmake
-            has_error &= relative_paths::clean_cmake_files(&lib.name);
+            result &= relative_paths::clean_cmake_files(&lib.name);
 
             // graph
-            has_error &= relative_paths::clean_graph_files();
+            result &= relative_paths::clean_graph_files();
 
             util::fs::set_cwd("..");
             util::fs::remove_dir(&lib.name);
 
             util::fs::set_cwd(&cwd);
         }
+        let package_dir = if project_conf.path.contains("/") {
+            util::fs::get_parent_dir(&project_conf.path)
+        } else {
+            cwd.clone()
+        };
+        for example in &project_conf.get_examples(&package_dir) {
+            util::fs::set_cwd(&format!(
+                "{}/{}",
+                relative_paths::ASC_PROJECT_DIR_NAME,
+                example.name
+            ));
+            tracing::info!(message = "clean example", name = example.name);
+
+            // cmake
+            result &= relative_paths::clean_cmake_files(&example.name);
+
+            // graph
+            result &= relative_paths::clean_graph_files();
+
+            util::fs::set_cwd("..");
+            util::fs::remove_dir(&example.name);
+
+            util::fs::set_cwd(&cwd);
+        }
+        for test in &project_conf.get_tests(&package_dir) {
+            util::fs::set_cwd(&format!(
+                "{}/{}",
+                relative_paths::ASC_PROJECT_DIR_NAME,
+                test.name
+            ));
+            tracing::info!(message = "clean test", name = test.name);
+
+            // cmake
+            result &= relative_paths::clean_cmake_files(&test.name);
+
+            // graph
+            result &= relative_paths::clean_graph_files();
+
+            util::fs::set_cwd("..");
+            util::fs::remove_dir(&test.name);
+
+            util::fs::set_cwd(&cwd);
+        }
 
         // cmake
         util::fs::set_cwd(relative_paths::ASC_PROJECT_DIR_NAME);
-        has_error = relative_paths::clean_cmake_files("");
+        result &= relative_paths::clean_cmake_files("");
         util::fs::set_cwd(&cwd);
 
         // target
-        has_error &= relative_paths::clean_target_files();
+        result &= relative_paths::clean_target_files();
 
-        return has_error;
+        return result;
     }
 
     fn clean_workspace(&self, package_conf: &config::project::ProjectConfig) -> bool {
@@ -89,15 +181,15 @@ impl CleanArgs {
         let cwd = util::fs::get_cwd();
 
         // members
-        let mut has_error = false;
+        let mut result = true;
         match &package_conf.workspace {
             None => {
-                has_error &= true;
+                result = false;
                 tracing::error!(error_tag = ErrorTag::InvalidProjectWorkspaceError.as_ref(),);
             }
             Some(workspace_config) => {
                 if workspace_config.members.is_empty() {
-                    has_error &= true;
+                    result = false;
                     tracing::error!(error_tag = ErrorTag::InvalidProjectWorkspaceError.as_ref(),);
                 }
                 for m in &workspace_config.members {
@@ -105,7 +197,7 @@ impl CleanArgs {
                         &format!("{}/{}/{}", cwd, m, relative_paths::ASC_TOML_FILE_NAME),
                         false,
                     ) {
-                        has_error &= self.clean_package(&project_conf);
+                        result &= self.clean_package(&project_conf);
                     }
                 }
             }
@@ -113,12 +205,12 @@ impl CleanArgs {
 
         // cmake
         util::fs::set_cwd(relative_paths::ASC_PROJECT_DIR_NAME);
-        has_error &= relative_paths::clean_cmake_files("");
+        result &= relative_paths::clean_cmake_files("");
         util::fs::set_cwd(&cwd);
 
         // target
-        has_error &= relative_paths::clean_target_files();
+        result &= relative_paths::clean_target_files();
 
-        return has_error;
+        return result;
     }
 }
@@ -0,0 +1,307 @@
+use std::collections::{BTreeSet, HashMap};
+
+use clap::Args;
+
+use super::{scan::ScanOptions, ConfigType};
+use crate::{
+    cmake,
+    config::{
+        self,
+        project::{ProjectConfig, ScannedFiles},
+        relative_paths,
+    },
+    errors::ErrorTag,
+    util,
+};
+
+#[derive(Args, Debug, Default, Clone)]
+/// build and run tests with coverage, output lcov and html reports to target/coverage
+pub struct CoverageArgs {
+    /// run single test (default all)
+    #[clap(long)]
+    pub test: Option<String>,
+
+    /// use clang source based coverage (-fprofile-instr-generate) instead of gcov (--coverage)
+    #[clap(long, default_value_t = false)]
+    pub llvm: bool,
+}
+
+impl CoverageArgs {
+    pub fn exec(&self) -> bool {
+        tracing::info!(message = "coverage", name = util::fs::get_cwd_name());
+
+        if !config::project::ProjectConfig::is_project_inited(false) {
+            return false;
+        }
+
+        if !config::project::ProjectConfig::is_source_scaned() {
+            return false;
+        }
+
+        let project_conf = match config::project::ProjectConfig::read_project_conf() {
+            None => return false,
+            Some(c) => c,
+        };
+
+        let mut tests = project_conf
+            .flatten_tests()
+            .into_iter()
+            .map(|t| t.name)
+            .collect::<Vec<String>>();
+        if let Some(name) = &self.test {
+            if !tests.contains(name) {
+                tracing::error!(
+                    error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                    tests = tests.join(", ")
+                );
+                return false;
+            }
+            tests = vec![name.clone()];
+        }
+        if tests.is_empty() {
+            tracing::error!(
+                error_tag = ErrorTag::InvalidProjectError.as_ref(),
+                message = "no tests were found"
+            );
+            return false;
+        }
+
+        let cwd = util::fs::get_cwd();
+        let coverage_dir = format!("{cwd}/{}", relative_paths::ASC_TARGET_COVERAGE_DIR);
+        let mut options = ScanOptions {
+            project_dir: format!("{cwd}/{}", relative_paths::ASC_PROJECT_DIR_NAME),
+            target_dir: format!("{coverage_dir}/build"),
+            cmake_config: ConfigType::Debug.as_ref().to_string(),
+            coverage: String::from(if self.llvm { "llvm" } else { "gcov" }),
+            ..Default::default()
+        };
+
+        // configure and build tests with coverage flags
+        cmake::project::gen(&options);
+        for name in &tests {
+            options.project = name.clone();
+            cmake::build::exec(&options);
+        }
+
+        // clear last results
+        let profraw_dir = format!("{coverage_dir}/profraw");
+        util::fs::remove_dirs(&profraw_dir);
+        util::fs::create_dirs(&profraw_dir);
+        if !self.llvm
+            && !run_tool(
+                "lcov",
+                &vec!["--zerocounters", "--directory", &options.target_dir],
+            )
+        {
+            return false;
+        }
+
+        // run tests
+        let mut executables = vec![];
+        let mut failed = vec![];
+        for name in &tests {
+            let path = format!(
+                "{}/{}/{}/{}",
+                options.target_dir, name, options.cmake_config, name
+            );
+            let mut envs = HashMap::new();
+            envs.insert(
+                String::from("LLVM_PROFILE_FILE"),
+                format!("{profraw_dir}/{name}-%p.profraw"),
+            );
+            let passed =
+                match util::shell::run_with_envs(&path, &vec![], ".", &envs, false, false, false) {
+                    Ok(output) => output.status.success(),
+                    Err(_) => false,
+                };
+            if !passed {
+                failed.push(name.clone());
+            }
+            executables.push(path);
+        }
+        if !failed.is_empty() {
+            tracing::warn!(
+                error_tag = ErrorTag::TestFailedError.as_ref(),
+                failed = failed.join(", ")
+            );
+        }
+
+        // only sources which were scanned from bins and libs, third-party code was excluded
+        let files = scanned_files(&project_conf, &cwd);
+        if files.is_empty() {
+            tracing::error!(
+                error_tag = ErrorTag::FileNotFoundError.as_ref(),
+                path = relative_paths::SCANNED_FILES_TOML_FILE_NAME,
+                message = "please run asc scan first"
+            );
+            return false;
+        }
+
+        let lcov_info = format!("{coverage_dir}/lcov.info");
+        let ok = if self.llvm {
+            self.report_llvm(
+                &profraw_dir,
+                &executables,
+                &files,
+                &coverage_dir,
+                &lcov_info,
+            )
+        } else {
+            self.report_gcov(&options.target_dir, &files, &coverage_dir, &lcov_info)
+        };
+        if !ok {
+            return false;
+        }
+
+        let html_dir = format!("{coverage_dir}/html");
+        if !run_tool(
+            "genhtml",
+            &vec![&lcov_info, "--output-directory", &html_dir],
+        ) {
+            return false;
+        }
+
+        tracing::warn!(
+            message = "coverage report",
+            lcov = lcov_info,
+            html = html_dir
+        );
+        return failed.is_empty();
+    }
+
+    fn report_gcov(
+        &self,
+        build_dir: &str,
+        files: &Vec<String>,
+        coverage_dir: &str,
+        lcov_info: &str,
+    ) -> bool {
+        let raw_info = format!("{coverage_dir}/lcov.raw.info");
+        if !run_tool(
+            "lcov",
+            &vec![
+                "--capture",
+                "--directory",
+                build_dir,
+                "--output-file",
+                &raw_info,
+            ],
+        ) {
+            return false;
+        }
+
+        let mut args = vec!["--extract", raw_info.as_str()];
+        args.extend(files.iter().map(|s| s.as_str()));
+        args.extend(["--output-file", lcov_info]);
+        return run_tool("lcov", &args);
+    }
+
+    fn report_llvm(
+        &self,
+        profraw_dir: &str,
+        executables: &Vec<String>,
+        files: &Vec<String>,
+        coverage_dir: &str,
+        lcov_info: &str,
+    ) -> bool {
+        let profdata = format!("{coverage_dir}/merged.profdata");
+        let profraws = match std::fs::read_dir(profraw_dir) {
+            Err(_) => vec![],
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path().to_str().unwrap().replace(r"\", "/"))
+                .filter(|p| p.ends_with(".profraw"))
+                .collect::<Vec<String>>(),
+        };
+        let mut args = vec!["merge", "-sparse"];
+        args.extend(profraws.iter().map(|s| s.as_str()));
+        args.extend(["-o", profdata.as_str()]);
+        if !run_tool("llvm-profdata", &args) {
+            return false;
+        }
+
+        let instr_profile = format!("-instr-profile={profdata}");
+        let mut args = vec!["export", "-format=lcov", instr_profile.as_str()];
+        for (i, exe) in executables.iter().enumerate() {
+            if i > 0 {
+                args.push("-object");
+            }
+            args.push(exe);
+        }
+        args.extend(files.iter().map(|s| s.as_str()));
+        match util::shell::run("llvm-cov", &args, ".", true, false, false) {
+            Ok(output) if output.status.success() => {
+                if let Err(e) = std::fs::write(lcov_info, &output.stdout) {
+                    tracing::error!(
+                        func = "std::fs::write",
+                        path = lcov_info,
+                        error_tag = ErrorTag::WriteFileError.as_ref(),
+                        error_str = e.to_string(),
+                    );
+                    return false;
+                }
+                return true;
+            }
+            _ => {
+                tracing::error!(
+                    func = "util::shell::run",
+                    command = "llvm-cov",
+                    error_tag = ErrorTag::ExecuteCommandError.as_ref(),
+                );
+                return false;
+            }
+        }
+    }
+}
+
+fn run_tool(command: &str, args: &Vec<&str>) -> bool {
+    match util::shell::run(command, args, ".", false, false, false) {
+        Ok(output) if output.status.success() => {
+            return true;
+        }
+        _ => {
+            tracing::error!(
+                func = "util::shell::run",
+                command = command,
+                error_tag = ErrorTag::ExecuteCommandError.as_ref(),
+            );
+            return false;
+        }
+    }
+}
+
+fn scanned_files(project_conf: &ProjectConfig, cwd: &str) -> Vec<String> {
+    let mut names = vec![];
+    match &project_conf.workspace {
+        None => {
+            names.extend(project_conf.bins.iter().map(|e| e.name.clone()));
+            names.extend(project_conf.libs.iter().map(|e| e.name.clone()));
+        }
+        Some(workspace) => {
+            for member in &workspace.members {
+                if let Some(member_conf) = ProjectConfig::load(
+                    &format!("{cwd}/{member}/{}", relative_paths::ASC_TOML_FILE_NAME),
+                    false,
+                ) {
+                    names.extend(member_conf.bins.iter().map(|e| e.name.clone()));
+                    names.extend(member_conf.libs.iter().map(|e| e.name.clone()));
+                }
+            }
+        }
+    }
+
+    let mut files = BTreeSet::new();
+    for name in names {
+        if let Some(data) = ScannedFiles::load(
+            &format!(
+                "{cwd}/{}/{name}/{}",
+                relative_paths::ASC_PROJECT_DIR_NAME,
+                relative_paths::SCANNED_FILES_TOML_FILE_NAME
+            ),
+            true,
+        ) {
+            files.extend(data.files);
+        }
+    }
+    return files.into_iter().collect();
+}
@@ -0,0 +1,23 @@
+use clap::Args;
+
+use crate::dependency;
+
+#[derive(Args, Debug, Clone)]
+/// check dependencies of package or workspace members against scanned sources
+pub struct DepsArgs {
+    /// report vcpkg dependencies whose headers were not included and libraries were not linked
+    #[clap(long, default_value_t = false)]
+    pub unused: bool,
+
+    /// remove unused dependencies from asc.toml after confirmation
+    #[clap(long, default_value_t = false)]
+    pub remove: bool,
+}
+
+impl DepsArgs {
+    pub fn exec(&self) -> bool {
+        tracing::info!(message = "deps", unused = self.unused);
+
+        return dependency::unused::check(self);
+    }
+}
@@ -0,0 +1,26 @@
+use clap::Args;
+
+use crate::dependency;
+
+#[derive(Args, Debug, Clone)]
+/// print description, features, dependencies and versions of vcpkg port
+pub struct InfoArgs {
+    /// port name
+    pub port: String,
+
+    /// port version or requirement (default latest, --version=11.0.2, --version=^10)
+    #[clap(long, default_value = "*")]
+    pub version: String,
+
+    /// vcpkg registry name, resolved by registry priority and packages if omitted
+    #[clap(long, default_value = "")]
+    pub registry: String,
+}
+
+impl InfoArgs {
+    pub fn exec(&self) -> bool {
+        tracing::info!(message = "info", port = self.port, version = self.version);
+
+        return dependency::info::print(self);
+    }
+}
@@ -90,6 +90,7 @@ impl InitArgs {
                 shared: if self.shared { Some(true) } else { Some(false) },
                 std_c: String::new(),
                 std_cxx: String::new(),
+                ..Default::default()
             }]);
         } else {
             project.bins = BTreeSet::from([EntryConfig {
@@ -99,6 +100,7 @@ impl InitArgs {
                 shared: None,
                 std_c: String::new(),
                 std_cxx: String::new(),
+                ..Default::default()
             }]);
         }
 
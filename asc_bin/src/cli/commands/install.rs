@@ -1,6 +1,6 @@
 use clap::Args;
 
-use super::{scan::ScanOptions, ConfigType};
+use super::{select_profile, select_triplets, ConfigType};
 use crate::{cmake, config, config::relative_paths, util};
 
 #[derive(Args, Debug, Default, Clone)]
@@ -14,9 +14,17 @@ pub struct InstallArgs {
     #[clap(long, default_value_t = false)]
     release: bool,
 
+    /// build profile (debug, release, relwithdebinfo, minsizerel or [profile.<name>] in asc.toml)
+    #[clap(long)]
+    profile: Option<String>,
+
     /// package cli (7z, tar, iscc, auto .7z on windows .tar.xz on others)
     #[clap(long, default_value = "")]
     pack_cli: String,
+
+    /// vcpkg target triplet (default [vcpkg] target_triplet in asc.toml or host), installed from target/<triplet>
+    #[clap(long)]
+    target_triplet: Option<String>,
 }
 
 impl InstallArgs {
@@ -31,12 +39,20 @@ impl InstallArgs {
             return false;
         }
 
-        let options = ScanOptions {
-            target_dir: relative_paths::ASC_TARGET_DIR_NAME.to_string(),
-            cmake_config: ConfigType::from(self.release).as_ref().to_string(),
-            ..Default::default()
+        let (mut options, profile) = match select_profile(self.release, &self.profile, &vec![]) {
+            None => return false,
+            Some(p) => p,
+        };
+        if !select_triplets(&mut options, &self.target_triplet, &None, &None) {
+            return false;
+        }
+        // vcpkg only installs debug and release libraries
+        let vcpkg_profile = if profile.inherits == ConfigType::Debug.profile_name() {
+            ConfigType::Debug.profile_name()
+        } else {
+            ConfigType::Release.profile_name()
         };
-        cmake::install::exec(&options, &self.prefix, &self.pack_cli);
+        cmake::install::exec(&options, &self.prefix, &self.pack_cli, &vcpkg_profile);
 
         return true;
     }
@@ -2,8 +2,16 @@ pub mod add;
 pub use add::AddArgs;
 pub mod build;
 pub use build::BuildArgs;
+pub mod cache;
+pub use cache::CacheArgs;
 pub mod clean;
 pub use clean::CleanArgs;
+pub mod coverage;
+pub use coverage::CoverageArgs;
+pub mod deps;
+pub use deps::DepsArgs;
+pub mod info;
+pub use info::InfoArgs;
 pub mod init;
 pub use init::InitArgs;
 pub mod new;
@@ -14,6 +22,10 @@ pub mod run;
 pub use run::RunArgs;
 pub mod search;
 pub use search::SearchArgs;
+pub mod test;
+pub use test::TestArgs;
+pub mod tree;
+pub use tree::TreeArgs;
 pub mod scan;
 pub use scan::ScanArgs;
 pub mod install;
@@ -22,6 +34,8 @@ pub mod publish;
 pub use publish::PublishArgs;
 pub mod uninstall;
 pub use uninstall::UninstallArgs;
+pub mod update;
+pub use update::UpdateArgs;
 pub mod vcpkg;
 pub use vcpkg::VcpkgArgs;
 
@@ -44,13 +58,20 @@ pub enum Commands {
 
     Vcpkg(VcpkgArgs),
     Search(SearchArgs),
+    Info(InfoArgs),
     Add(AddArgs),
     Remove(RemoveArgs),
+    Update(UpdateArgs),
+    Tree(TreeArgs),
+    Deps(DepsArgs),
+    Cache(CacheArgs),
 
     Scan(ScanArgs),
     Build(BuildArgs),
 
     Run(RunArgs),
+    Test(TestArgs),
+    Coverage(CoverageArgs),
     Clean(CleanArgs),
 
     Install(InstallArgs),
@@ -65,6 +86,8 @@ pub enum ConfigType {
     #[default]
     Debug,
     Release,
+    RelWithDebInfo,
+    MinSizeRel,
 }
 
 impl From<bool> for ConfigType {
@@ -77,6 +100,110 @@ impl From<bool> for ConfigType {
     }
 }
 
+impl ConfigType {
+    // built-in profile name (debug, release, relwithdebinfo, minsizerel)
+    pub fn from_profile(name: &str) -> Option<Self> {
+        for i in 0.. {
+            match Self::from_repr(i) {
+                None => break,
+                Some(c) => {
+                    if c.as_ref().to_lowercase() == name.to_lowercase() {
+                        return Some(c);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    pub fn profile_name(&self) -> String {
+        self.as_ref().to_lowercase()
+    }
+}
+
+// --profile takes precedence over --release, --sanitize adds to the profile's sanitizers
+// returns build options (target dir, cmake config, sanitizers) and the resolved profile
+pub fn select_profile(
+    release: bool,
+    profile: &Option<String>,
+    sanitize: &Vec<String>,
+) -> Option<(scan::ScanOptions, crate::config::project::ProfileConfig)> {
+    let name = match profile {
+        Some(p) => match ConfigType::from_profile(p) {
+            Some(c) => c.profile_name(),
+            None => p.clone(),
+        },
+        None => ConfigType::from(release).profile_name(),
+    };
+
+    let profiles = match crate::config::project::ProjectConfig::read_project_conf() {
+        Some(project_conf) => project_conf.profiles,
+        None => return None,
+    };
+
+    match crate::config::project::ProfileConfig::resolve(&profiles, &name) {
+        None => {
+            return None;
+        }
+        Some(mut resolved) => {
+            resolved.sanitize.extend(sanitize.clone());
+            resolved.sanitize = crate::util::sanitizer::normalize(&resolved.sanitize)?;
+
+            let options = scan::ScanOptions {
+                target_dir: crate::config::relative_paths::profile_target_dir(
+                    &name,
+                    &resolved.sanitize,
+                ),
+                cmake_config: crate::config::project::ProfileConfig::cmake_config(&name),
+                sanitizers: resolved.sanitize.clone(),
+                ..Default::default()
+            };
+            return Some((options, resolved));
+        }
+    }
+}
+
+// cli args take precedence over [vcpkg] in asc.toml, host triplet was used if both were omitted
+// builds of non-host triplets or chainloaded toolchain were moved into target/<triplet>[_...]
+pub fn select_triplets(
+    options: &mut scan::ScanOptions,
+    target_triplet: &Option<String>,
+    host_triplet: &Option<String>,
+    toolchain_file: &Option<String>,
+) -> bool {
+    let cwd = crate::util::fs::get_cwd();
+    let mut vcpkg_conf = crate::config::project::VcpkgConfig::default();
+    match crate::config::project::ProjectConfig::read_project_conf() {
+        None => return false,
+        Some(project_conf) => vcpkg_conf.extend(&cwd, &project_conf.vcpkg),
+    }
+
+    let default_triplet = crate::cmake::project::default_vcpkg_triplet();
+    let select = |arg: &Option<String>, conf: &String| match arg {
+        Some(t) => t.clone(),
+        None if !conf.is_empty() => conf.clone(),
+        None => default_triplet.clone(),
+    };
+    options.target_triplet = select(target_triplet, &vcpkg_conf.target_triplet);
+    options.host_triplet = select(host_triplet, &vcpkg_conf.host_triplet);
+    options.toolchain_file = match toolchain_file {
+        Some(f) if std::path::Path::new(f).is_absolute() => crate::util::fs::normalize_path(f),
+        Some(f) => crate::util::fs::normalize_path(&format!("{cwd}/{f}")),
+        None => vcpkg_conf.toolchain_file,
+    };
+
+    if let Some(name) = crate::config::relative_paths::triplet_dir_name(
+        &options.target_triplet,
+        &options.host_triplet,
+        &options.toolchain_file,
+        &default_triplet,
+    ) {
+        options.target_dir =
+            crate::config::relative_paths::triplet_target_dir(&options.target_dir, &name);
+    }
+    return true;
+}
+
 #[derive(Clone, Debug, Default, ValueEnum, AsRefStr, FromRepr)]
 #[clap(rename_all = "snake_case")]
 pub enum VcpkgAction {
@@ -86,4 +213,16 @@ pub enum VcpkgAction {
     Get,
     Index,
     Flatten,
+    Overlay,
+    Doctor,
+}
+
+#[derive(Clone, Debug, Default, ValueEnum, AsRefStr, FromRepr)]
+#[clap(rename_all = "snake_case")]
+pub enum CacheAction {
+    #[default]
+    Size,
+    Prune,
+    Export,
+    Import,
 }
@@ -214,6 +214,22 @@ impl NewArgs {
             }
         }
 
+        {
+            // write examples/example.cpp
+            let dir = format!("{}/{}", name, relative_paths::EXAMPLES_DIR_NAME);
+            util::fs::create_dirs(&dir);
+            let path = format!("{}/{}", dir, relative_paths::EXAMPLE_CPP_FILE_NAME);
+            if let Err(e) = std::fs::write(&path, templates::EXAMPLE_CPP_HBS.as_bytes()) {
+                tracing::error!(
+                    func = "std::fs::write",
+                    path = path,
+                    error_tag = ErrorTag::WriteFileError.as_ref(),
+                    error_str = e.to_string(),
+                );
+                return false;
+            }
+        }
+
         return true;
     }
 
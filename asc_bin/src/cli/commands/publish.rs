@@ -88,11 +88,13 @@ impl PublishArgs {
                     git::log::get_latest_commit(".", git::log::GIT_LOG_FORMAT_COMMIT_HASH_DATE);
 
                 let vcpkg_conf = VcpkgArgs::load_or_default();
-                let (_name, _url, _branch, repo_root_dir) =
+                let (_name, url, _branch, repo_root_dir) =
                     vcpkg_conf.get_private_registry(&self.registry);
                 if repo_root_dir.is_empty() {
                     return false;
                 }
+                // filesystem registry was written in place without git
+                let filesystem = VcpkgArgs::is_filesystem_url(&url);
                 let dir =
                     config::system_paths::DataPath::vcpkg_ports_dir_path(&repo_root_dir, &pkg.name);
                 let action = if util::fs::is_dir_exists(&dir) {
@@ -108,7 +110,7 @@ impl PublishArgs {
                     &latest_commit,
                 );
                 result &= vcpkg::cmake::gen_port_file_cmake(&repo_root_dir, pkg, &latest_commit);
-                if result {
+                if result && !filesystem {
                     git::add::run(&vec![dir], &repo_root_dir);
                     git::commit::run(
                         format!(
@@ -119,8 +121,9 @@ impl PublishArgs {
                     );
                 }
 
-                result &= vcpkg::json::gen_port_versions(&repo_root_dir, pkg, port_version);
-                if result {
+                result &=
+                    vcpkg::json::gen_port_versions(&repo_root_dir, pkg, port_version, filesystem);
+                if result && !filesystem {
                     git::add::run(
                         &vec![
                             config::system_paths::DataPath::vcpkg_versions_port_json_path(
@@ -1,12 +1,12 @@
 use std::collections::BTreeSet;
 
 use crate::{
-    config::{self, project::ProjectConfig, relative_paths},
+    config::{self, project::ProjectConfig},
     errors::ErrorTag,
     util,
 };
 
-use super::ConfigType;
+use super::{select_profile, select_triplets};
 
 use clap::Args;
 
@@ -17,6 +17,10 @@ pub struct RunArgs {
     #[clap(long)]
     bin: Option<String>,
 
+    /// example name
+    #[clap(long)]
+    example: Option<String>,
+
     /// command line arguments
     #[clap(long)]
     args: Option<Vec<String>>,
@@ -24,6 +28,26 @@ pub struct RunArgs {
     /// release mode (default false)
     #[clap(long, default_value_t = false)]
     release: bool,
+
+    /// build profile (debug, release, relwithdebinfo, minsizerel or [profile.<name>] in asc.toml)
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// run sanitizer build (address, undefined, thread, memory, leak)
+    #[clap(long, value_delimiter = ',')]
+    sanitize: Vec<String>,
+
+    /// run build of vcpkg target triplet (default [vcpkg] target_triplet in asc.toml or host)
+    #[clap(long)]
+    target_triplet: Option<String>,
+
+    /// run build of vcpkg host triplet (default [vcpkg] host_triplet in asc.toml or host)
+    #[clap(long)]
+    host_triplet: Option<String>,
+
+    /// run build of chainloaded cmake toolchain file (default [vcpkg] toolchain_file in asc.toml)
+    #[clap(long)]
+    toolchain_file: Option<String>,
 }
 
 impl RunArgs {
@@ -31,6 +55,10 @@ impl RunArgs {
         tracing::info!(message = "run");
 
         if let Some(project_conf) = config::project::ProjectConfig::read_project_conf() {
+            if self.example.is_some() {
+                return self.run_example(&project_conf);
+            }
+
             if let Some(workspace) = project_conf.workspace {
                 let cwd = util::fs::get_cwd();
                 let mut flat_project_conf = config::project::ProjectConfig::default();
@@ -81,13 +109,43 @@ impl RunArgs {
             );
             return false;
         }
-        return util::shell::run(
+        return self.run_executable(&bin_name);
+    }
+
+    fn run_example(&self, project_conf: &ProjectConfig) -> bool {
+        let examples = project_conf.flatten_examples();
+        let name = self.example.as_ref().unwrap();
+        if !examples.iter().any(|e| &e.name == name) {
+            tracing::error!(
+                error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                examples = examples
+                    .iter()
+                    .map(|e| e.name.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+            return false;
+        }
+        return self.run_executable(name);
+    }
+
+    fn run_executable(&self, name: &str) -> bool {
+        let mut options = match select_profile(self.release, &self.profile, &self.sanitize) {
+            None => return false,
+            Some((options, _)) => options,
+        };
+        if !select_triplets(
+            &mut options,
+            &self.target_triplet,
+            &self.host_triplet,
+            &self.toolchain_file,
+        ) {
+            return false;
+        }
+        return util::shell::run_with_envs(
             &format!(
                 "{}/{}/{}/{}",
-                relative_paths::ASC_TARGET_DIR_NAME,
-                bin_name,
-                ConfigType::from(self.release).as_ref(),
-                bin_name
+                options.target_dir, name, options.cmake_config, name
             ),
             &self
                 .args
@@ -97,6 +155,7 @@ impl RunArgs {
                 .map(|s| s.as_str())
                 .collect(),
             ".",
+            &util::sanitizer::runtime_envs(&options.sanitizers),
             false,
             false,
             false,
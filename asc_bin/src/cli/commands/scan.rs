@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use clap::Args;
 
@@ -6,14 +6,22 @@ use crate::clang;
 use crate::cmake;
 use crate::config;
 use crate::config::project::DependencyConfig;
+use crate::config::project::EntryConfig;
+use crate::config::project::ProfileConfig;
 use crate::config::project::ProjectConfig;
+use crate::config::project::ScannedFiles;
 use crate::config::project::StdDependencyConfig;
+use crate::config::project::VcpkgConfig;
+use crate::config::project::WorkSpaceConfig;
 use crate::config::relative_paths;
+use crate::dependency;
 use crate::errors::ErrorTag;
 use crate::graph;
 use crate::util;
 use crate::vcpkg;
 
+use super::select_triplets;
+
 #[derive(Clone, Debug, Default)]
 pub struct ScanOptions {
     pub project: String,
@@ -26,7 +34,28 @@ pub struct ScanOptions {
     pub std_c: String,
     pub std_cxx: String,
     pub cmake_config: String,
+    pub defines: Vec<String>,
+    pub cflags: Vec<String>,
+    pub cxxflags: Vec<String>,
+    pub ldflags: Vec<String>,
+    pub include_dirs: Vec<String>,
+    pub link_libraries: Vec<String>,
+    pub sanitizers: Vec<String>,
+    pub coverage: String,
     pub cmake_minimum_version: String,
+    // host triplet if empty
+    pub target_triplet: String,
+    pub host_triplet: String,
+    pub toolchain_file: String,
+}
+
+// entries of package were scanned in this order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    Bin,
+    Lib,
+    Example,
+    Test,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -35,10 +64,36 @@ pub struct ScanArgs {
     /// for cmake cmake_minimum_required
     #[clap(long, default_value = "3.20")]
     pub cmake_minimum_version: String,
+
+    /// suggest vcpkg ports for unresolved #include <...> and offer to add them
+    #[clap(long, default_value_t = false)]
+    pub suggest_deps: bool,
+
+    /// generate only, git and path dependencies were configured by dependents
+    #[clap(skip)]
+    pub skip_configure: bool,
+
+    /// generate .asc and target into this dir instead of the package dir, vcpkg manifest was skipped
+    #[clap(skip)]
+    pub output_dir: String,
 }
 
 impl ScanArgs {
     pub fn exec(&self) -> bool {
+        let cwd = util::fs::get_cwd();
+        if !self.scan() {
+            return false;
+        }
+
+        if self.suggest_deps {
+            util::fs::set_cwd(&cwd);
+            return dependency::suggest::missing_dependencies(&cwd);
+        }
+
+        return true;
+    }
+
+    fn scan(&self) -> bool {
         if !config::project::ProjectConfig::is_project_inited(false) {
             return false;
         }
@@ -59,133 +114,222 @@ impl ScanArgs {
                 }
 
                 // cd .asc
-                if !util::fs::is_dir_exists(relative_paths::ASC_PROJECT_DIR_NAME) {
-                    util::fs::create_dir(relative_paths::ASC_PROJECT_DIR_NAME);
-                }
                 let cwd = util::fs::get_cwd();
-                util::fs::set_cwd(relative_paths::ASC_PROJECT_DIR_NAME);
-
-                let mut members = vec![];
-                for bin_entry in &project_conf.bins {
-                    members.push(bin_entry.name.clone());
-
-                    if !util::fs::is_dir_exists(&bin_entry.name) {
-                        util::fs::create_dir(&bin_entry.name);
-                    }
-                    let c = util::fs::get_cwd();
-                    // cd bin_entry.name
-                    util::fs::set_cwd(&bin_entry.name);
-
-                    self.scan_package(
-                        &bin_entry.name,
-                        &cwd,
-                        &format!("{cwd}/{}", bin_entry.source_dir),
-                        &format!("{cwd}/{}/{}", bin_entry.source_dir, bin_entry.source_file),
-                        &format!(
-                            "{cwd}/{}/{}",
-                            relative_paths::ASC_TARGET_DIR_NAME,
-                            bin_entry.name
-                        ),
-                        true,
-                        &project_conf.dependencies,
-                        &project_conf.std_dependencies,
-                        false,
-                        false,
-                        &bin_entry.std_c,
-                        &bin_entry.std_cxx,
-                    );
-
-                    // cd .asc
-                    util::fs::set_cwd(&c);
+                let out = self.output_root(&cwd);
+                let project_dir = format!("{out}/{}", relative_paths::ASC_PROJECT_DIR_NAME);
+                if !util::fs::is_dir_exists(&project_dir) {
+                    util::fs::create_dirs(&project_dir);
                 }
 
-                for lib_entry in &project_conf.libs {
-                    members.push(lib_entry.name.clone());
+                // git and path dependencies
+                let source_deps = match self.resolve_source_dependencies(
+                    &project_conf.dependencies,
+                    &cwd,
+                    &project_dir,
+                    |_| false,
+                ) {
+                    None => return false,
+                    Some(source_deps) => source_deps,
+                };
+                let mut source_libs = BTreeMap::new();
+                let mut source_cmake_dirs = BTreeMap::new();
+                let mut dependencies = project_conf.dependencies.clone();
+                for source_dep in source_deps {
+                    source_libs.extend(source_dep.libs);
+                    source_cmake_dirs.insert(source_dep.name, source_dep.cmake_dir);
+                    dependencies.extend(source_dep.dependencies);
+                }
 
-                    if !util::fs::is_dir_exists(&lib_entry.name) {
-                        util::fs::create_dir(&lib_entry.name);
-                    }
-                    let c = util::fs::get_cwd();
-                    // cd lib_entry.name
-                    util::fs::set_cwd(&lib_entry.name);
-
-                    let is_shared_lib = lib_entry.shared.unwrap();
-                    self.scan_package(
-                        &lib_entry.name,
-                        &cwd,
-                        &format!("{cwd}/{}", lib_entry.source_dir),
-                        &format!("{cwd}/{}/{}", lib_entry.source_dir, lib_entry.source_file),
-                        &format!(
-                            "{cwd}/{}/{}",
-                            relative_paths::ASC_TARGET_DIR_NAME,
-                            lib_entry.name
-                        ),
-                        true,
-                        &project_conf.dependencies,
-                        &project_conf.std_dependencies,
-                        is_shared_lib,
-                        !is_shared_lib,
-                        &lib_entry.std_c,
-                        &lib_entry.std_cxx,
-                    );
+                util::fs::set_cwd(&project_dir);
 
-                    // cd .asc
-                    util::fs::set_cwd(&c);
-                }
+                let profiles = ProfileConfig::resolve_all(&project_conf.profiles);
+                let mut members = vec![];
+                let mut examples = vec![];
+                let mut tests = vec![];
+                self.scan_entries(
+                    &cwd,
+                    &cwd,
+                    &out,
+                    &project_conf,
+                    &source_libs,
+                    &profiles,
+                    &mut members,
+                    &mut examples,
+                    &mut tests,
+                );
 
                 cmake::lists::gen_workspace(
                     &self.cmake_minimum_version,
                     &project_conf.package.unwrap().name,
                     &members,
+                    &examples,
+                    &tests,
+                    &source_cmake_dirs,
+                    &profiles,
                 );
 
-                tracing::warn!("generate vcpkg manifest");
-                vcpkg::json::gen_vcpkg_configurations(&project_conf.dependencies);
+                // vcpkg dependencies of git and path dependencies were merged into dependents
+                if self.output_dir.is_empty() {
+                    tracing::warn!("generate vcpkg manifest");
+                    let mut vcpkg_conf = VcpkgConfig::default();
+                    vcpkg_conf.extend(&cwd, &project_conf.vcpkg);
+                    if !vcpkg::json::gen_vcpkg_configurations(
+                        &dependencies,
+                        &vcpkg_conf,
+                        &format!("{cwd}/{}", relative_paths::ASC_LOCK_FILE_NAME),
+                    ) {
+                        return false;
+                    }
+                }
 
-                tracing::warn!("generate a build system with cmake");
-                let options = ScanOptions {
-                    project_dir: format!("{cwd}/{}", relative_paths::ASC_PROJECT_DIR_NAME),
-                    target_dir: format!("{cwd}/{}", relative_paths::ASC_TARGET_DIR_NAME),
-                    shared_lib: false,
-                    ..Default::default()
-                };
-                cmake::project::gen(&options);
+                if !self.skip_configure {
+                    util::fs::set_cwd(&cwd);
+                    return self.configure(&project_dir, &out, false);
+                }
 
                 return true;
             }
         }
     }
 
-    pub fn scan_package(
+    // bins, libs, examples and tests of package, each was generated into .asc/<name>
+    // and built into target/<name>, returns libs of package for examples, tests and dependents
+    fn scan_entries(
         &self,
-        name: &str,
         root_dir: &str,
-        src_dir: &str,
-        src_path: &str,
-        taget_dir: &str,
-        is_workspace: bool,
-        dependencies: &BTreeMap<String, DependencyConfig>,
-        std_dependencies: &BTreeMap<String, StdDependencyConfig>,
-        is_shared_lib: bool,
-        is_static_lib: bool,
-        std_c: &str,
-        std_cxx: &str,
-    ) -> bool {
-        tracing::info!(message = "scan package", name = name);
+        package_dir: &str,
+        out: &str,
+        project_conf: &ProjectConfig,
+        dep_libs: &BTreeMap<String, String>,
+        profiles: &BTreeMap<String, ProfileConfig>,
+        members: &mut Vec<String>,
+        examples: &mut Vec<String>,
+        tests: &mut Vec<String>,
+    ) -> BTreeMap<String, String> {
+        let mut package_libs = dep_libs.clone();
+        package_libs.extend(project_conf.libs.iter().map(|lib| {
+            (
+                lib.name.clone(),
+                format!("{package_dir}/{}", lib.source_dir),
+            )
+        }));
+
+        let entries = project_conf
+            .bins
+            .iter()
+            .map(|e| (EntryKind::Bin, e.clone()))
+            .chain(
+                project_conf
+                    .libs
+                    .iter()
+                    .map(|e| (EntryKind::Lib, e.clone())),
+            )
+            .chain(
+                project_conf
+                    .get_examples(package_dir)
+                    .into_iter()
+                    .map(|e| (EntryKind::Example, e)),
+            )
+            .chain(
+                project_conf
+                    .get_tests(package_dir)
+                    .into_iter()
+                    .map(|e| (EntryKind::Test, e)),
+            )
+            .collect::<Vec<(EntryKind, EntryConfig)>>();
+
+        for (kind, entry) in &entries {
+            // examples and tests link libs of package too
+            let libs = match kind {
+                EntryKind::Bin | EntryKind::Lib => {
+                    members.push(entry.name.clone());
+                    dep_libs
+                }
+                EntryKind::Example => {
+                    examples.push(entry.name.clone());
+                    &package_libs
+                }
+                EntryKind::Test => {
+                    tests.push(entry.name.clone());
+                    &package_libs
+                }
+            };
+
+            if !util::fs::is_dir_exists(&entry.name) {
+                util::fs::create_dir(&entry.name);
+            }
+            let c = util::fs::get_cwd();
+            // cd entry.name
+            util::fs::set_cwd(&entry.name);
+
+            self.scan_package(
+                &self.entry_options(root_dir, package_dir, out, *kind, entry),
+                &project_conf.dependencies,
+                &project_conf.std_dependencies,
+                libs,
+                profiles,
+            );
+
+            // cd .asc
+            util::fs::set_cwd(&c);
+        }
+
+        return package_libs;
+    }
 
-        let options = ScanOptions {
-            project: name.to_string(),
+    pub fn entry_options(
+        &self,
+        root_dir: &str,
+        package_dir: &str,
+        out: &str,
+        kind: EntryKind,
+        entry: &EntryConfig,
+    ) -> ScanOptions {
+        let is_shared_lib = kind == EntryKind::Lib && entry.shared.unwrap();
+        return ScanOptions {
+            project: entry.name.clone(),
             project_dir: root_dir.to_string(),
-            target_dir: taget_dir.to_string(),
-            source_dir: src_dir.to_string(),
-            entry_point_source: src_path.to_string(),
+            target_dir: format!(
+                "{out}/{}/{}",
+                relative_paths::ASC_TARGET_DIR_NAME,
+                entry.name
+            ),
+            source_dir: format!("{package_dir}/{}", entry.source_dir),
+            entry_point_source: format!("{package_dir}/{}/{}", entry.source_dir, entry.source_file),
             shared_lib: is_shared_lib,
-            static_lib: is_static_lib,
-            std_c: std_c.to_string(),
-            std_cxx: std_cxx.to_string(),
+            static_lib: kind == EntryKind::Lib && !is_shared_lib,
+            std_c: entry.std_c.clone(),
+            std_cxx: entry.std_cxx.clone(),
+            defines: entry.defines.clone(),
+            cflags: entry.cflags.clone(),
+            cxxflags: entry.cxxflags.clone(),
+            ldflags: entry.ldflags.clone(),
+            include_dirs: entry
+                .include_dirs
+                .iter()
+                .map(|dir| {
+                    if std::path::Path::new(dir).is_absolute() {
+                        dir.replace(r"\", "/")
+                    } else {
+                        format!("{package_dir}/{}", dir.replace(r"\", "/"))
+                    }
+                })
+                .collect(),
+            link_libraries: entry.link_libraries.clone(),
             cmake_minimum_version: self.cmake_minimum_version.clone(),
             ..Default::default()
         };
+    }
+
+    pub fn scan_package(
+        &self,
+        options: &ScanOptions,
+        dependencies: &BTreeMap<String, DependencyConfig>,
+        std_dependencies: &BTreeMap<String, StdDependencyConfig>,
+        package_libs: &BTreeMap<String, String>,
+        profiles: &BTreeMap<String, ProfileConfig>,
+    ) -> bool {
+        tracing::info!(message = "scan package", name = options.project);
 
         tracing::info!("{:#?}", options);
 
@@ -222,16 +366,35 @@ impl ScanArgs {
             "output flow chart {}",
             relative_paths::FLOW_CHART_MD_FILE_NAME
         );
-        let mermaid_flowchart = graph::flowchart::gen(&options, &source_mappings);
+        let mermaid_flowchart = graph::flowchart::gen(options, &source_mappings);
         tracing::info!("\n{mermaid_flowchart}");
 
+        tracing::warn!("output {}", relative_paths::SCANNED_FILES_TOML_FILE_NAME);
+        let mut scanned_files = BTreeSet::new();
+        scanned_files.insert(options.entry_point_source.clone());
+        for (header, sources) in &source_mappings.header_include_by_sources {
+            scanned_files.insert(header.clone());
+            scanned_files.extend(sources.iter().cloned());
+        }
+        ScannedFiles {
+            files: scanned_files.into_iter().collect(),
+            third_party_includes: source_mappings.third_party_include_by_sources.clone(),
+            third_party_angled_includes: source_mappings
+                .third_party_angled_include_by_sources
+                .clone(),
+            path: relative_paths::SCANNED_FILES_TOML_FILE_NAME.to_string(),
+        }
+        .dump(true, false);
+
         tracing::warn!("output {}", relative_paths::CMAKE_LISTS_TXT_FILE_NAME);
         cmake::lists::gen(
-            &options,
+            options,
             &source_mappings,
-            is_workspace,
+            true,
             dependencies,
             std_dependencies,
+            package_libs,
+            profiles,
         );
 
         return true;
@@ -241,96 +404,89 @@ impl ScanArgs {
         tracing::info!(message = "scan workspace", name = util::fs::get_cwd_name());
 
         // cd .asc
-        if !util::fs::is_dir_exists(relative_paths::ASC_PROJECT_DIR_NAME) {
-            util::fs::create_dir(relative_paths::ASC_PROJECT_DIR_NAME);
-        }
         let cwd = util::fs::get_cwd();
-        util::fs::set_cwd(relative_paths::ASC_PROJECT_DIR_NAME);
+        let out = self.output_root(&cwd);
+        let project_dir = format!("{out}/{}", relative_paths::ASC_PROJECT_DIR_NAME);
+        if !util::fs::is_dir_exists(&project_dir) {
+            util::fs::create_dirs(&project_dir);
+        }
+        util::fs::set_cwd(&project_dir);
+
+        // profiles of workspace members were ignored
+        let profiles = ProfileConfig::resolve_all(&project_conf.profiles);
+        let workspace = project_conf.workspace.as_ref().unwrap();
+        let sorted_members = match workspace.load_sorted_members(&cwd) {
+            None => {
+                util::fs::set_cwd(&cwd);
+                return false;
+            }
+            Some(sorted_members) => sorted_members,
+        };
+        if !WorkSpaceConfig::check_entry_names(&cwd, &sorted_members) {
+            util::fs::set_cwd(&cwd);
+            return false;
+        }
 
-        let mut has_error = false;
         let mut members = vec![];
+        let mut examples = vec![];
+        let mut tests = vec![];
         let mut dependencies = BTreeMap::new();
         let is_shared_lib = false;
-        for member in &project_conf.workspace.as_ref().unwrap().members {
-            match config::project::ProjectConfig::load(
-                &format!("{cwd}/{member}/{}", relative_paths::ASC_TOML_FILE_NAME),
-                false,
+        // member -> lib target -> include dir, libs of path dependencies were included
+        let mut exported_libs = BTreeMap::<String, BTreeMap<String, String>>::new();
+        let mut source_cmake_dirs = BTreeMap::new();
+        // overlays of workspace and members
+        let mut vcpkg_conf = VcpkgConfig::default();
+        vcpkg_conf.extend(&cwd, &project_conf.vcpkg);
+        for (member, project_conf, dep_members) in &sorted_members {
+            // link libs of path dependencies
+            let mut dep_libs = BTreeMap::new();
+            for dep_member in dep_members {
+                if let Some(libs) = exported_libs.get(dep_member) {
+                    dep_libs.extend(libs.clone());
+                }
+            }
+
+            // git and path dependencies out of workspace
+            let source_deps = match self.resolve_source_dependencies(
+                &project_conf.dependencies,
+                &format!("{cwd}/{member}"),
+                &project_dir,
+                |dep| workspace.find_member(&cwd, member, dep).is_some(),
             ) {
                 None => {
-                    has_error = true;
+                    util::fs::set_cwd(&cwd);
+                    return false;
                 }
-                Some(project_conf) => {
-                    for bin_entry in &project_conf.bins {
-                        members.push(bin_entry.name.clone());
-
-                        if !util::fs::is_dir_exists(&bin_entry.name) {
-                            util::fs::create_dir(&bin_entry.name);
-                        }
-                        let c = util::fs::get_cwd();
-                        util::fs::set_cwd(&bin_entry.name);
-
-                        self.scan_package(
-                            &bin_entry.name,
-                            &cwd,
-                            &format!("{cwd}/{member}/{}", bin_entry.source_dir),
-                            &format!(
-                                "{cwd}/{member}/{}/{}",
-                                bin_entry.source_dir, bin_entry.source_file
-                            ),
-                            &format!(
-                                "{cwd}/{}/{}",
-                                relative_paths::ASC_TARGET_DIR_NAME,
-                                bin_entry.name
-                            ),
-                            true,
-                            &project_conf.dependencies,
-                            &project_conf.std_dependencies,
-                            false,
-                            false,
-                            &bin_entry.std_c,
-                            &bin_entry.std_cxx,
-                        );
-
-                        util::fs::set_cwd(&c);
-                    }
-
-                    for lib_entry in &project_conf.libs {
-                        members.push(lib_entry.name.clone());
-
-                        if !util::fs::is_dir_exists(&lib_entry.name) {
-                            util::fs::create_dir(&lib_entry.name);
-                        }
-                        let c = util::fs::get_cwd();
-                        util::fs::set_cwd(&lib_entry.name);
-
-                        let is_shared_lib = lib_entry.shared.unwrap();
-                        self.scan_package(
-                            &lib_entry.name,
-                            &cwd,
-                            &format!("{cwd}/{member}/{}", lib_entry.source_dir),
-                            &format!(
-                                "{cwd}/{member}/{}/{}",
-                                lib_entry.source_dir, lib_entry.source_file
-                            ),
-                            &format!(
-                                "{cwd}/{}/{}",
-                                relative_paths::ASC_TARGET_DIR_NAME,
-                                lib_entry.name
-                            ),
-                            true,
-                            &project_conf.dependencies,
-                            &project_conf.std_dependencies,
-                            is_shared_lib,
-                            !is_shared_lib,
-                            &lib_entry.std_c,
-                            &lib_entry.std_cxx,
-                        );
-
-                        util::fs::set_cwd(&c);
-                    }
+                Some(source_deps) => source_deps,
+            };
+            for source_dep in source_deps {
+                dep_libs.extend(source_dep.libs);
+                source_cmake_dirs.insert(source_dep.name, source_dep.cmake_dir);
+                dependencies.extend(source_dep.dependencies);
+            }
 
-                    dependencies.extend(project_conf.dependencies);
-                }
+            let package_libs = self.scan_entries(
+                &cwd,
+                &format!("{cwd}/{member}"),
+                &out,
+                project_conf,
+                &dep_libs,
+                &profiles,
+                &mut members,
+                &mut examples,
+                &mut tests,
+            );
+
+            exported_libs.insert(member.clone(), package_libs);
+            vcpkg_conf.extend(&format!("{cwd}/{member}"), &project_conf.vcpkg);
+            if !WorkSpaceConfig::merge_dependencies(
+                &mut dependencies,
+                member,
+                &project_conf.dependencies,
+            ) {
+                util::fs::set_cwd(&cwd);
+                return false;
             }
         }
 
@@ -338,22 +494,204 @@ impl ScanArgs {
             &self.cmake_minimum_version,
             &util::fs::get_file_name(&cwd),
             &members,
+            &examples,
+            &tests,
+            &source_cmake_dirs,
+            &profiles,
         );
 
-        tracing::warn!("generate vcpkg manifest");
-        vcpkg::json::gen_vcpkg_configurations(&dependencies);
+        // vcpkg dependencies of git and path dependencies were merged into dependents
+        if self.output_dir.is_empty() {
+            tracing::warn!("generate vcpkg manifest");
+            if !vcpkg::json::gen_vcpkg_configurations(
+                &dependencies,
+                &vcpkg_conf,
+                &format!("{cwd}/{}", relative_paths::ASC_LOCK_FILE_NAME),
+            ) {
+                util::fs::set_cwd(&cwd);
+                return false;
+            }
+        }
+
+        util::fs::set_cwd(&cwd);
+
+        if !self.skip_configure {
+            return self.configure(&project_dir, &out, is_shared_lib);
+        }
 
+        return true;
+    }
+
+    // triplets and toolchain file of asc.toml select the build directory, same as asc build
+    fn configure(&self, project_dir: &str, out: &str, shared_lib: bool) -> bool {
         tracing::warn!("generate a build system with cmake");
-        let options = ScanOptions {
-            project_dir: format!("{cwd}/{}", relative_paths::ASC_PROJECT_DIR_NAME),
-            target_dir: format!("{cwd}/{}", relative_paths::ASC_TARGET_DIR_NAME),
-            shared_lib: is_shared_lib,
+        let mut options = ScanOptions {
+            target_dir: relative_paths::ASC_TARGET_DIR_NAME.to_string(),
             ..Default::default()
         };
+        if !select_triplets(&mut options, &None, &None, &None) {
+            return false;
+        }
+        options.project_dir = project_dir.to_string();
+        options.target_dir = format!("{out}/{}", options.target_dir);
+        options.shared_lib = shared_lib;
         cmake::project::gen(&options);
+        return true;
+    }
 
-        util::fs::set_cwd(&cwd);
+    // dir of generated files, package dir unless generated for dependents
+    fn output_root(&self, cwd: &str) -> String {
+        if self.output_dir.is_empty() {
+            cwd.to_string()
+        } else {
+            self.output_dir.clone()
+        }
+    }
+
+    // git and path dependencies were generated into .asc/source_deps/<name> of dependents
+    fn resolve_source_dependencies<F>(
+        &self,
+        dependencies: &BTreeMap<String, DependencyConfig>,
+        package_dir: &str,
+        project_dir: &str,
+        is_workspace_member: F,
+    ) -> Option<Vec<dependency::source::SourceDependency>>
+    where
+        F: Fn(&DependencyConfig) -> bool,
+    {
+        let mut source_deps = vec![];
+        for (name, dep) in dependencies {
+            if !dep.is_source() || is_workspace_member(dep) {
+                continue;
+            }
+            tracing::warn!(message = "resolve source dependency", name = name);
+            source_deps.push(dependency::source::resolve(
+                name,
+                dep,
+                package_dir,
+                &format!(
+                    "{project_dir}/{}/{name}",
+                    relative_paths::ASC_SOURCE_DEPENDENCIES_DIR_NAME
+                ),
+                &self.cmake_minimum_version,
+            )?);
+        }
+        return Some(source_deps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_package(dir: &std::path::Path, name: &str, entry: &str, dependencies: &str) {
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join("asc.toml"),
+            format!(
+                r#"[package]
+name = "{name}"
+version = "2024.10.30"
+edition = "2024"
+description = ""
+license = ""
+repository = ""
+branch = "main"
+
+{entry}
+
+[dependencies]
+{dependencies}
+"#
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[ignore = "requires libclang download"]
+    fn test_scan_path_dependency() {
+        let dir = std::env::temp_dir().join(format!("asc_scan_path_dep_{}", std::process::id()));
+        let dep_dir = dir.join("dep");
+        let app_dir = dir.join("app");
+
+        write_package(
+            &dep_dir,
+            "dep",
+            "[[lib]]\nname = \"dep\"\nsource_dir = \"src\"\nsource_file = \"lib.cpp\"\nshared = false",
+            "",
+        );
+        std::fs::write(dep_dir.join("src").join("lib.hpp"), "int dep();\n").unwrap();
+        std::fs::write(
+            dep_dir.join("src").join("lib.cpp"),
+            "#include \"lib.hpp\"\nint dep() { return 1; }\n",
+        )
+        .unwrap();
 
-        return has_error;
+        write_package(
+            &app_dir,
+            "app",
+            "[[bin]]\nname = \"app\"\nsource_dir = \"src\"\nsource_file = \"main.cpp\"",
+            "dep = { path = \"../dep\" }",
+        );
+        std::fs::write(
+            app_dir.join("src").join("main.cpp"),
+            "#include <lib.hpp>\nint main() { return dep(); }\n",
+        )
+        .unwrap();
+
+        let cwd = util::fs::get_cwd();
+        util::fs::set_cwd(&app_dir.to_string_lossy());
+        let scanned = ScanArgs {
+            cmake_minimum_version: String::from("3.20"),
+            suggest_deps: false,
+            skip_configure: true,
+            output_dir: String::new(),
+        }
+        .exec();
+        util::fs::set_cwd(&cwd);
+        assert!(scanned);
+
+        // source tree of path dependency was untouched
+        assert!(!dep_dir.join(relative_paths::ASC_PROJECT_DIR_NAME).exists());
+        assert!(!dep_dir.join(relative_paths::ASC_TARGET_DIR_NAME).exists());
+        assert!(!dep_dir.join(relative_paths::ASC_LOCK_FILE_NAME).exists());
+
+        let dep_project_dir = app_dir
+            .join(relative_paths::ASC_PROJECT_DIR_NAME)
+            .join(relative_paths::ASC_SOURCE_DEPENDENCIES_DIR_NAME)
+            .join("dep")
+            .join(relative_paths::ASC_PROJECT_DIR_NAME);
+        let workspace_cmake = std::fs::read_to_string(
+            app_dir
+                .join(relative_paths::ASC_PROJECT_DIR_NAME)
+                .join(relative_paths::CMAKE_LISTS_TXT_FILE_NAME),
+        )
+        .unwrap();
+        assert!(workspace_cmake.contains(&format!(
+            "add_subdirectory(\"{}\"",
+            dep_project_dir.to_string_lossy().replace(r"\", "/")
+        )));
+
+        // version.h and config.h were configured in the dir of the dependency
+        let dep_cmake = std::fs::read_to_string(
+            dep_project_dir
+                .join("dep")
+                .join(relative_paths::CMAKE_LISTS_TXT_FILE_NAME),
+        )
+        .unwrap();
+        assert!(dep_cmake.contains(
+            "configure_file(${CMAKE_CURRENT_SOURCE_DIR}/version.h.in ${CMAKE_CURRENT_BINARY_DIR}/version.h @ONLY)"
+        ));
+        assert!(dep_cmake.contains(
+            "configure_file(${CMAKE_CURRENT_SOURCE_DIR}/config.h.cm ${CMAKE_CURRENT_BINARY_DIR}/config.h)"
+        ));
+        assert!(!dep_cmake.contains("${CMAKE_SOURCE_DIR}"));
+        assert!(dep_cmake.contains(&format!(
+            "{}/src",
+            dep_dir.to_string_lossy().replace(r"\", "/")
+        )));
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
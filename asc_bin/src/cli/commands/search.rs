@@ -3,23 +3,45 @@ use clap::Args;
 use crate::vcpkg;
 
 #[derive(Args, Debug, Clone)]
-/// search package with extractly name or startswith/endswith/contains text
+/// search package by ranked fuzzy name, features and description, or startswith/endswith/contains text
 pub struct SearchArgs {
-    /// extractly match (spdlog), startswith (log*), endswith (*log), contains (*log*)
+    /// fuzzy match (spdlgo, "logging library"), startswith (log*), endswith (*log), contains (*log*)
     pub name: String,
 
-    /// list all versions
+    /// list all versions of extractly matched port
     #[clap(long, default_value_t = false)]
     pub list: bool,
+
+    /// output version, registry, description and features as json
+    #[clap(long, default_value_t = false)]
+    pub json: bool,
 }
 
 impl SearchArgs {
     pub fn exec(&self) -> bool {
         tracing::info!(message = "search", name = self.name);
 
-        let results = vcpkg::search::from_index_file(&self.name, self.list);
-        for res in &results {
-            tracing::info!("{}", res);
+        if self.list {
+            let results = vcpkg::search::from_index_file(&self.name, self.list);
+            for res in &results {
+                tracing::info!("{}", res);
+            }
+            return !results.is_empty();
+        }
+
+        let results = vcpkg::search::search(&self.name);
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&results).unwrap());
+        } else {
+            for res in &results {
+                tracing::info!(
+                    "[{}]  {}  {}  {}",
+                    res.registry,
+                    res.name,
+                    res.version,
+                    res.description
+                );
+            }
         }
 
         return !results.is_empty();
@@ -0,0 +1,153 @@
+use clap::Args;
+
+use super::{scan::ScanOptions, select_profile, select_triplets};
+use crate::{cmake, config, config::relative_paths, errors::ErrorTag, util};
+
+#[derive(Args, Debug, Default, Clone)]
+/// build and run tests (tests/*.cpp or [[test]] in asc.toml)
+pub struct TestArgs {
+    /// run single test (default all)
+    #[clap(long)]
+    pub test: Option<String>,
+
+    /// command line arguments
+    #[clap(long)]
+    args: Option<Vec<String>>,
+
+    /// release mode (default false)
+    #[clap(long, default_value_t = false)]
+    release: bool,
+
+    /// build profile (debug, release, relwithdebinfo, minsizerel or [profile.<name>] in asc.toml)
+    #[clap(long)]
+    pub profile: Option<String>,
+
+    /// build with sanitizers (address, undefined, thread, memory, leak)
+    #[clap(long, value_delimiter = ',')]
+    pub sanitize: Vec<String>,
+
+    /// vcpkg target triplet (default [vcpkg] target_triplet in asc.toml or host), built in target/<triplet>
+    #[clap(long)]
+    pub target_triplet: Option<String>,
+
+    /// vcpkg host triplet (default [vcpkg] host_triplet in asc.toml or host)
+    #[clap(long)]
+    pub host_triplet: Option<String>,
+
+    /// cmake toolchain file chainloaded by vcpkg (default [vcpkg] toolchain_file in asc.toml)
+    #[clap(long)]
+    pub toolchain_file: Option<String>,
+}
+
+impl TestArgs {
+    pub fn exec(&self) -> bool {
+        tracing::info!(message = "test", name = util::fs::get_cwd_name());
+
+        if !config::project::ProjectConfig::is_project_inited(false) {
+            return false;
+        }
+
+        if !config::project::ProjectConfig::is_source_scaned() {
+            return false;
+        }
+
+        let project_conf = match config::project::ProjectConfig::read_project_conf() {
+            None => return false,
+            Some(c) => c,
+        };
+
+        let mut tests = project_conf
+            .flatten_tests()
+            .into_iter()
+            .map(|t| t.name)
+            .collect::<Vec<String>>();
+        if let Some(name) = &self.test {
+            if !tests.contains(name) {
+                tracing::error!(
+                    error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                    tests = tests.join(", ")
+                );
+                return false;
+            }
+            tests = vec![name.clone()];
+        }
+        if tests.is_empty() {
+            tracing::warn!(message = "no tests were found");
+            return true;
+        }
+
+        let mut options = match select_profile(self.release, &self.profile, &self.sanitize) {
+            None => return false,
+            Some((options, _)) => options,
+        };
+        if !select_triplets(
+            &mut options,
+            &self.target_triplet,
+            &self.host_triplet,
+            &self.toolchain_file,
+        ) {
+            return false;
+        }
+
+        // configure profile or triplet build directory
+        if options.target_dir != relative_paths::ASC_TARGET_DIR_NAME {
+            let cwd = util::fs::get_cwd();
+            cmake::project::gen(&ScanOptions {
+                project_dir: format!("{cwd}/{}", relative_paths::ASC_PROJECT_DIR_NAME),
+                target_dir: format!("{cwd}/{}", options.target_dir),
+                cmake_config: options.cmake_config.clone(),
+                sanitizers: options.sanitizers.clone(),
+                target_triplet: options.target_triplet.clone(),
+                host_triplet: options.host_triplet.clone(),
+                toolchain_file: options.toolchain_file.clone(),
+                ..Default::default()
+            });
+        }
+
+        // tests were excluded from all
+        for name in &tests {
+            options.project = name.clone();
+            cmake::build::exec(&options);
+        }
+
+        let envs = util::sanitizer::runtime_envs(&options.sanitizers);
+        let args = match &self.args {
+            None => vec![],
+            Some(a) => a.iter().map(|s| s.as_str()).collect::<Vec<&str>>(),
+        };
+        let mut failed = vec![];
+        for name in &tests {
+            tracing::warn!(message = "run test", name = name);
+            let passed = match util::shell::run_with_envs(
+                &format!(
+                    "{}/{}/{}/{}",
+                    options.target_dir, name, options.cmake_config, name
+                ),
+                &args,
+                ".",
+                &envs,
+                false,
+                false,
+                false,
+            ) {
+                Ok(output) => output.status.success(),
+                Err(_) => false,
+            };
+            if !passed {
+                failed.push(name.clone());
+            }
+        }
+
+        if !failed.is_empty() {
+            tracing::error!(
+                error_tag = ErrorTag::TestFailedError.as_ref(),
+                passed = tests.len() - failed.len(),
+                failed = failed.join(", ")
+            );
+            return false;
+        }
+
+        tracing::warn!(message = "all tests passed", count = tests.len());
+        return true;
+    }
+}
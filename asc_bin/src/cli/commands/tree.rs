@@ -0,0 +1,27 @@
+use clap::Args;
+
+use crate::dependency;
+
+#[derive(Args, Debug, Clone)]
+/// print transitive dependency tree of vcpkg ports at locked versions
+pub struct TreeArgs {
+    /// print ports which depend on the port
+    #[clap(long)]
+    pub invert: Option<String>,
+
+    /// print requested features and platform expressions
+    #[clap(long, default_value_t = false)]
+    pub features: bool,
+
+    /// max depth of tree
+    #[clap(long)]
+    pub depth: Option<usize>,
+}
+
+impl TreeArgs {
+    pub fn exec(&self) -> bool {
+        tracing::info!(message = "tree", invert = self.invert);
+
+        return dependency::tree::print(self);
+    }
+}
@@ -0,0 +1,33 @@
+use clap::Args;
+
+use crate::dependency;
+
+#[derive(Args, Debug, Clone)]
+/// upgrade dependencies in package or workspace members' asc.toml and refresh asc.lock
+pub struct UpdateArgs {
+    /// dependency names (default all)
+    pub dependencies: Vec<String>,
+
+    /// workspace member name (default all)
+    #[clap(long)]
+    pub package: Option<String>,
+
+    /// show available upgrades without rewriting asc.toml and asc.lock
+    #[clap(long, default_value_t = false)]
+    pub dry_run: bool,
+
+    /// upgrade to the latest version even if the major version was changed
+    #[clap(long, default_value_t = false)]
+    pub breaking: bool,
+}
+
+impl UpdateArgs {
+    pub fn exec(&self) -> bool {
+        tracing::info!(
+            message = "update",
+            dependencies = self.dependencies.join(", ")
+        );
+
+        return dependency::update::dependencies_in_config_file(self);
+    }
+}
@@ -15,16 +15,16 @@ use super::VcpkgAction;
 
 #[derive(Args, Clone, Debug, Default, Deserialize, Serialize, Iterable, ConfigFile)]
 #[config_file_ext("toml")]
-/// update vcpkg source, build vcpkg versions index, set/get vcpkg configurations
+/// update vcpkg source, build vcpkg versions index, set/get vcpkg configurations, check registries
 pub struct VcpkgArgs {
-    /// update/index/set/get
+    /// update/index/set/get/flatten/overlay/doctor
     #[serde(skip)]
     action: VcpkgAction,
 
-    /// update args
+    /// update args, overlay port name and version requirement (asc vcpkg overlay fmt ^10)
     pub args: Vec<String>,
 
-    /// vcpkg registry url?branch=&directory=
+    /// vcpkg registry url?branch=&directory=&priority=&packages= (packages=fmt,boost-*), file:///dir for filesystem registry
     #[clap(long)]
     pub registry: Vec<String>,
 
@@ -40,6 +40,10 @@ pub struct VcpkgArgs {
     #[clap(long)]
     pub env_default_binary_cache: Option<String>,
 
+    /// vcpkg binary sources (clear;files,/dir,readwrite;http,https://host/{sha}.zip,read)
+    #[clap(long)]
+    pub env_binary_sources: Option<String>,
+
     #[clap(long, default_value = "")]
     #[serde(skip)]
     pub path: String,
@@ -59,10 +63,33 @@ impl VcpkgArgs {
             }
             VcpkgAction::Index => manager.index(),
             VcpkgAction::Flatten => manager.flatten(),
+            VcpkgAction::Overlay => manager.overlay(),
+            VcpkgAction::Doctor => manager.doctor(),
         }
     }
 
+    // (name, url, branch, directory), descending priority then declaration order
     pub fn flatten_registry(&self) -> Vec<(String, String, String, String)> {
+        return self
+            .parse_registry()
+            .into_iter()
+            .map(|(name, url, branch, directory, _priority, _packages)| {
+                (name, url, branch, directory)
+            })
+            .collect();
+    }
+
+    // (name, priority, package patterns), descending priority then declaration order
+    pub fn flatten_registry_scopes(&self) -> Vec<(String, i64, Vec<String>)> {
+        return self
+            .parse_registry()
+            .into_iter()
+            .map(|(name, _url, _branch, _directory, priority, packages)| (name, priority, packages))
+            .collect();
+    }
+
+    // (name, url, branch, directory, priority, package patterns)
+    pub fn parse_registry(&self) -> Vec<(String, String, String, String, i64, Vec<String>)> {
         let mut results = vec![];
 
         for reg in &self.registry {
@@ -77,29 +104,75 @@ impl VcpkgArgs {
                 Ok(u) => {
                     let url = u.as_str().split('?').next().unwrap();
 
-                    let name = u.path().rsplit_once("/").unwrap().1.replace(".git", "");
+                    let name = u
+                        .path()
+                        .trim_end_matches('/')
+                        .rsplit_once("/")
+                        .unwrap()
+                        .1
+                        .replace(".git", "");
                     let mut branch = String::new();
                     let mut directory = String::new();
+                    let mut priority = 0;
+                    let mut packages = vec![];
 
                     for (key, value) in u.query_pairs() {
                         match key.as_ref() {
                             "branch" => branch = value.to_string(),
                             "directory" => directory = value.to_string(),
+                            "priority" => match value.parse::<i64>() {
+                                Ok(p) => priority = p,
+                                Err(e) => {
+                                    tracing::error!(
+                                        message = "invalid registry priority",
+                                        repo = reg,
+                                        error = e.to_string()
+                                    );
+                                }
+                            },
+                            "packages" => packages.extend(
+                                value
+                                    .split(',')
+                                    .map(|p| p.trim().to_string())
+                                    .filter(|p| !p.is_empty()),
+                            ),
                             _ => {}
                         }
                     }
 
                     if directory.is_empty() {
-                        directory = config::system_paths::DataPath::vcpkg_registry_clone_dir(&name);
+                        directory = if Self::is_filesystem_url(url) {
+                            // used in place instead of being cloned
+                            u.to_file_path()
+                                .map(|p| p.to_string_lossy().replace(r"\", "/"))
+                                .unwrap_or_default()
+                        } else {
+                            config::system_paths::DataPath::vcpkg_registry_clone_dir(&name)
+                        };
                     }
 
-                    results.push((name, url.to_string(), branch, directory));
+                    results.push((name, url.to_string(), branch, directory, priority, packages));
                 }
             }
         }
+
+        // stable, registries with same priority keep declaration order
+        results.sort_by(|a, b| b.4.cmp(&a.4));
         return results;
     }
 
+    // file:///dir was a filesystem registry (ports/ and versions/ in a local directory)
+    pub fn is_filesystem_url(url: &str) -> bool {
+        return url.starts_with("file:");
+    }
+
+    pub fn is_filesystem_registry(&self, name: &str) -> bool {
+        return self
+            .flatten_registry()
+            .iter()
+            .any(|(n, url, _branch, _directory)| n == name && Self::is_filesystem_url(url));
+    }
+
     pub fn get_registry(&self, name: &str) -> (String, String, String, String) {
         for (n, url, branch, directory) in self.flatten_registry() {
             if &n == name {
@@ -5,9 +5,18 @@ use crate::{
     dependency, pack, util,
 };
 
-pub fn exec(options: &cli::commands::scan::ScanOptions, prefix: &str, pack_cli: &str) {
+pub fn exec(
+    options: &cli::commands::scan::ScanOptions,
+    prefix: &str,
+    pack_cli: &str,
+    vcpkg_profile: &str,
+) {
     // run cmake --install
-    let triplet = default_vcpkg_triplet();
+    let triplet = if options.target_triplet.is_empty() {
+        default_vcpkg_triplet()
+    } else {
+        options.target_triplet.clone()
+    };
     let install_prefix = format!("{prefix}/{triplet}");
     let args = vec![
         "--install",
@@ -46,7 +55,7 @@ pub fn exec(options: &cli::commands::scan::ScanOptions, prefix: &str, pack_cli:
     // copy dependent libraries
     dependency::copy::copy_dependent_libraries(
         &install_prefix,
-        options.cmake_config.to_lowercase(),
+        vcpkg_profile.to_string(),
         &triplet,
         executable_and_dynamic_library_files,
     );
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::clang;
 use crate::cli;
 use crate::config::project::DependencyConfig;
+use crate::config::project::ProfileConfig;
 use crate::config::project::StdDependencyConfig;
 use crate::config::relative_paths;
 use crate::templates;
@@ -28,10 +29,24 @@ struct InstallHeader {
     dst: String,
 }
 
+#[derive(Default, Debug, Deserialize, Serialize)]
+struct ProfileData {
+    config: String,
+    config_upper: String,
+    definitions: Vec<String>,
+    compile_options: Vec<String>,
+    link_options: Vec<String>,
+    msvc_compile_options: Vec<String>,
+    msvc_link_options: Vec<String>,
+    lto: String,
+}
+
 #[derive(Default, Debug, Deserialize, Serialize)]
 struct CMakeListsData {
     cmake_version: String,
     is_workspace: bool,
+    // package or workspace root, the generated files may be out of it
+    project_dir: String,
     project: String,
     project_upper: String,
     build_year: i32,
@@ -50,8 +65,16 @@ struct CMakeListsData {
     include_directories: Vec<String>,
     find_packages: Vec<String>,
     private_libraries: Vec<String>,
+    package_libraries: Vec<String>,
     std_libraries: Vec<(String, String)>,
     install_headers: Vec<InstallHeader>,
+    profiles: Vec<ProfileData>,
+    entry_scope: String,
+    entry_defines: Vec<String>,
+    entry_compile_options: Vec<String>,
+    entry_link_options: Vec<String>,
+    entry_include_directories: Vec<String>,
+    entry_link_libraries: Vec<String>,
 }
 
 pub fn gen(
@@ -60,6 +83,8 @@ pub fn gen(
     is_workspace: bool,
     dependencies: &BTreeMap<String, DependencyConfig>,
     link_std_dependencies: &BTreeMap<String, StdDependencyConfig>,
+    package_libraries: &BTreeMap<String, String>,
+    profiles: &BTreeMap<String, ProfileConfig>,
 ) {
     // output default config.in.cm if not exists
     if !util::fs::is_file_exists(relative_paths::CONFIG_H_CM_FILE_NAME) {
@@ -79,6 +104,84 @@ pub fn gen(
         .unwrap()
     }
 
+    let data = gen_data(
+        options,
+        source_mappings,
+        is_workspace,
+        dependencies,
+        link_std_dependencies,
+        package_libraries,
+        profiles,
+    );
+
+    // render template
+    {
+        // write project-config.cmake.in
+        let reg = Handlebars::new();
+        let text = reg
+            .render_template(templates::PROJECT_CONFIG_CMAKE_IN_HBS, &data)
+            .unwrap();
+        std::fs::write(
+            relative_paths::get_config_cmake_in_file_name(&options.project),
+            text.as_bytes(),
+        )
+        .unwrap();
+    }
+
+    {
+        // write version.h.in
+        let reg = Handlebars::new();
+        let text = reg
+            .render_template(templates::VERSION_H_IN_HBS, &data)
+            .unwrap();
+        std::fs::write(relative_paths::VERSION_H_IN_FILE_NAME, text.as_bytes()).unwrap();
+    }
+
+    {
+        // write CMakeLists.txt
+        let reg = Handlebars::new();
+        let text = reg
+            .render_template(templates::PROJECT_CMAKE_LISTS_TXT_HBS, &data)
+            .unwrap();
+        std::fs::write(relative_paths::CMAKE_LISTS_TXT_FILE_NAME, text.as_bytes()).unwrap();
+    }
+}
+
+// CMakeLists.txt of package entry without writing any file
+pub fn render(
+    options: &cli::commands::scan::ScanOptions,
+    source_mappings: &clang::parser::SourceMappings,
+    is_workspace: bool,
+    dependencies: &BTreeMap<String, DependencyConfig>,
+    link_std_dependencies: &BTreeMap<String, StdDependencyConfig>,
+    package_libraries: &BTreeMap<String, String>,
+    profiles: &BTreeMap<String, ProfileConfig>,
+) -> String {
+    let data = gen_data(
+        options,
+        source_mappings,
+        is_workspace,
+        dependencies,
+        link_std_dependencies,
+        package_libraries,
+        profiles,
+    );
+    let reg = Handlebars::new();
+    return reg
+        .render_template(templates::PROJECT_CMAKE_LISTS_TXT_HBS, &data)
+        .unwrap();
+}
+
+// user.cmake was read from cwd
+fn gen_data(
+    options: &cli::commands::scan::ScanOptions,
+    source_mappings: &clang::parser::SourceMappings,
+    is_workspace: bool,
+    dependencies: &BTreeMap<String, DependencyConfig>,
+    link_std_dependencies: &BTreeMap<String, StdDependencyConfig>,
+    package_libraries: &BTreeMap<String, String>,
+    profiles: &BTreeMap<String, ProfileConfig>,
+) -> CMakeListsData {
     // group data
     let (group_sources, classify_to_dir, install_headers) = group_sources(options, source_mappings);
 
@@ -86,6 +189,7 @@ pub fn gen(
     let local_date_time = chrono::prelude::Local::now();
     let mut data = CMakeListsData::default();
     data.is_workspace = is_workspace;
+    data.project_dir = options.project_dir.clone();
     data.cmake_version = options.cmake_minimum_version.clone();
     data.project = options.project.clone();
     data.project_upper = options.project.to_uppercase();
@@ -135,6 +239,36 @@ pub fn gen(
         data.std_libraries
             .push((dep.name.clone(), dep.check.clone()));
     }
+    // libraries of the own package (examples, tests) and path dependencies
+    for (name, include_dir) in package_libraries {
+        data.package_libraries.push(name.clone());
+        data.include_directories.push(include_dir.clone());
+    }
+
+    // libs export their usage requirements to dependents
+    data.entry_scope = String::from(if data.library { "PUBLIC" } else { "PRIVATE" });
+    data.entry_defines = options.defines.clone();
+    for flag in &options.cflags {
+        data.entry_compile_options
+            .push(format!("$<$<COMPILE_LANGUAGE:C>:{flag}>"));
+    }
+    for flag in &options.cxxflags {
+        data.entry_compile_options
+            .push(format!("$<$<COMPILE_LANGUAGE:CXX>:{flag}>"));
+    }
+    data.entry_link_options = options.ldflags.clone();
+    for dir in &options.include_dirs {
+        data.entry_include_directories.push(if data.library {
+            format!("$<BUILD_INTERFACE:{dir}>")
+        } else {
+            dir.clone()
+        });
+    }
+    data.entry_link_libraries = options.link_libraries.clone();
+
+    for (name, profile) in profiles {
+        data.profiles.push(gen_profile(name, profile));
+    }
 
     for (dir, sources) in &group_sources {
         let mut group = SourcesGroup::default();
@@ -152,52 +286,144 @@ pub fn gen(
             .push(InstallHeader { src: src, dst: dst });
     }
 
-    // render template
-    {
-        // write project-config.cmake.in
-        let reg = Handlebars::new();
-        let text = reg
-            .render_template(templates::PROJECT_CONFIG_CMAKE_IN_HBS, &data)
-            .unwrap();
-        std::fs::write(
-            relative_paths::get_config_cmake_in_file_name(&options.project),
-            text.as_bytes(),
-        )
-        .unwrap();
+    return data;
+}
+
+fn gen_profile(name: &str, profile: &ProfileConfig) -> ProfileData {
+    let config = ProfileConfig::cmake_config(name);
+    let for_config = |flag: &str| format!("$<$<CONFIG:{config}>:{flag}>");
+    let for_config_lang = |lang: &str, flag: &str| {
+        format!("$<$<AND:$<CONFIG:{config}>,$<COMPILE_LANGUAGE:{lang}>>:{flag}>")
+    };
+
+    let mut data = ProfileData::default();
+    data.config_upper = config.to_uppercase();
+
+    // optimization level
+    let (opt, msvc_opt) = match profile.opt_level.as_str() {
+        "" => ("", ""),
+        "0" => ("-O0", "/Od"),
+        "1" => ("-O1", "/O2"),
+        "2" => ("-O2", "/O2"),
+        "3" => ("-O3", "/O2"),
+        "s" => ("-Os", "/O1"),
+        "z" => ("-Oz", "/O1"),
+        other => {
+            tracing::warn!(
+                message = "unknown opt_level",
+                opt_level = other,
+                profile = name
+            );
+            ("", "")
+        }
+    };
+    if !opt.is_empty() {
+        data.compile_options.push(for_config(opt));
+        data.msvc_compile_options.push(for_config(msvc_opt));
     }
 
-    {
-        // write version.h.in
-        let reg = Handlebars::new();
-        let text = reg
-            .render_template(templates::VERSION_H_IN_HBS, &data)
-            .unwrap();
-        std::fs::write(relative_paths::VERSION_H_IN_FILE_NAME, text.as_bytes()).unwrap();
+    // debug information
+    match profile.debug {
+        Some(true) => {
+            data.compile_options.push(for_config("-g"));
+            data.msvc_compile_options.push(for_config("/Zi"));
+            data.msvc_link_options.push(for_config("/DEBUG"));
+        }
+        Some(false) => {
+            data.compile_options.push(for_config("-g0"));
+        }
+        None => {}
     }
 
-    {
-        // write CMakeLists.txt
-        let reg = Handlebars::new();
-        let text = reg
-            .render_template(templates::PROJECT_CMAKE_LISTS_TXT_HBS, &data)
-            .unwrap();
-        std::fs::write(relative_paths::CMAKE_LISTS_TXT_FILE_NAME, text.as_bytes()).unwrap();
+    // user flags were passed to any compiler as is
+    for flag in &profile.cflags {
+        let f = for_config_lang("C", flag);
+        data.compile_options.push(f.clone());
+        data.msvc_compile_options.push(f);
+    }
+    for flag in &profile.cxxflags {
+        let f = for_config_lang("CXX", flag);
+        data.compile_options.push(f.clone());
+        data.msvc_compile_options.push(f);
+    }
+    for flag in &profile.ldflags {
+        let f = for_config(flag);
+        data.link_options.push(f.clone());
+        data.msvc_link_options.push(f);
     }
+    for define in &profile.defines {
+        data.definitions.push(for_config(define));
+    }
+
+    if let Some(lto) = profile.lto {
+        data.lto = String::from(if lto { "ON" } else { "OFF" });
+    }
+
+    data.config = config;
+    return data;
 }
 
-pub fn gen_workspace(cmake_minimum_version: &str, project: &str, members: &Vec<String>) {
+pub fn gen_workspace(
+    cmake_minimum_version: &str,
+    project: &str,
+    members: &Vec<String>,
+    examples: &Vec<String>,
+    tests: &Vec<String>,
+    source_dependencies: &BTreeMap<String, String>,
+    profiles: &BTreeMap<String, ProfileConfig>,
+) {
+    // write CMakeLists.txt
+    let text = render_workspace(
+        cmake_minimum_version,
+        project,
+        members,
+        examples,
+        tests,
+        source_dependencies,
+        profiles,
+    );
+    std::fs::write(relative_paths::CMAKE_LISTS_TXT_FILE_NAME, text.as_bytes()).unwrap();
+}
+
+// source dependencies were added by their generated cmake dirs
+pub fn render_workspace(
+    cmake_minimum_version: &str,
+    project: &str,
+    members: &Vec<String>,
+    examples: &Vec<String>,
+    tests: &Vec<String>,
+    source_dependencies: &BTreeMap<String, String>,
+    profiles: &BTreeMap<String, ProfileConfig>,
+) -> String {
+    // custom profiles were added as new cmake configurations based on the inherited built-in one
+    let custom_profiles = profiles
+        .iter()
+        .filter(|(name, _)| !ProfileConfig::is_builtin(name))
+        .map(|(name, profile)| {
+            let base = ProfileConfig::cmake_config(&profile.inherits);
+            serde_json::json!({
+                "name": name,
+                "upper": name.to_uppercase(),
+                "base": base,
+                "base_upper": base.to_uppercase(),
+            })
+        })
+        .collect::<Vec<serde_json::Value>>();
+
     let data = serde_json::json!({
         "cmake_version": cmake_minimum_version,
         "project": project,
         "members": members,
+        "examples": examples,
+        "tests": tests,
+        "source_dependencies": source_dependencies,
+        "custom_profiles": custom_profiles,
     });
 
-    // write CMakeLists.txt
     let reg = Handlebars::new();
-    let text = reg
+    return reg
         .render_template(templates::WORKSPACE_CMAKE_LISTS_TXT_HBS, &data)
         .unwrap();
-    std::fs::write(relative_paths::CMAKE_LISTS_TXT_FILE_NAME, text.as_bytes()).unwrap();
 }
 
 fn group_sources(
@@ -212,18 +438,29 @@ fn group_sources(
     let mut group_sources = BTreeMap::<String, BTreeSet<String>>::new();
     let mut classify_to_dir = HashMap::<String, String>::new();
     let mut install_headers = BTreeMap::<String, String>::new();
+    // files generated for dependents were out of the package dir, absolute paths were kept
+    let in_package_dir = options
+        .target_dir
+        .starts_with(&format!("{}/", options.project_dir));
+    let relative = |path: &String| {
+        if in_package_dir {
+            util::fs::replace_common_prefix(
+                path,
+                &options.source_dir,
+                &options.target_dir,
+                "../../",
+            )
+        } else {
+            path.clone()
+        }
+    };
     for (header, sources) in &source_mappings.header_include_by_sources {
         {
             let header_locate_dir = util::fs::get_parent_dir(header);
 
             // prepare install headers's src and dst
             let src = if header.starts_with(&options.source_dir) {
-                util::fs::replace_common_prefix(
-                    &header,
-                    &options.source_dir,
-                    &options.target_dir,
-                    "../../",
-                )
+                relative(&header)
             } else {
                 format!(
                     "${{CMAKE_CURRENT_BINARY_DIR}}/{}",
@@ -250,12 +487,7 @@ fn group_sources(
             let dir = util::fs::get_parent_dir(&relative_path);
             let classify = dir.replace("/", "_");
             classify_to_dir.insert(classify.clone(), dir.to_string());
-            let header_path = util::fs::replace_common_prefix(
-                &header,
-                &options.source_dir,
-                &options.target_dir,
-                "../../",
-            );
+            let header_path = relative(&header);
             group_sources
                 .entry(classify.to_string())
                 .or_default()
@@ -270,12 +502,7 @@ fn group_sources(
                 let dir = util::fs::get_parent_dir(&relative_path);
                 let classify = dir.replace("/", "_");
                 classify_to_dir.insert(classify.clone(), dir.to_string());
-                let src_path = util::fs::replace_common_prefix(
-                    &src,
-                    &options.source_dir,
-                    &options.target_dir,
-                    "../../",
-                );
+                let src_path = relative(&src);
                 group_sources
                     .entry(classify.to_string())
                     .or_default()
@@ -284,5 +511,111 @@ fn group_sources(
         }
     }
 
+    // entry point source without any local inclusion
+    if !options.entry_point_source.is_empty() {
+        let src_path = relative(&options.entry_point_source);
+        if !group_sources
+            .values()
+            .any(|files| files.contains(&src_path))
+        {
+            let relative_path: String = util::fs::remove_prefix(
+                &options.entry_point_source,
+                &options.project_dir,
+                &options.target_dir,
+            );
+            let dir = util::fs::get_parent_dir(&relative_path);
+            let classify = dir.replace("/", "_");
+            classify_to_dir.insert(classify.clone(), dir.to_string());
+            group_sources.entry(classify).or_default().insert(src_path);
+        }
+    }
+
     return (group_sources, classify_to_dir, install_headers);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::cli::commands::scan::ScanOptions;
+
+    #[test]
+    fn test_render_source_dependency() {
+        // app depends on lib dep of path dependency, which was generated into app/.asc/source_deps
+        let dep_project_dir = "/work/app/.asc/source_deps/dep/.asc";
+        let workspace = render_workspace(
+            "3.20",
+            "app",
+            &vec![String::from("app")],
+            &vec![],
+            &vec![],
+            &BTreeMap::from([(String::from("dep"), dep_project_dir.to_string())]),
+            &BTreeMap::new(),
+        );
+        assert!(workspace.contains(&format!(
+            "add_subdirectory(\"{dep_project_dir}\" \"${{CMAKE_BINARY_DIR}}/_deps/dep\" EXCLUDE_FROM_ALL)"
+        )));
+        assert!(workspace.contains("add_subdirectory(app)"));
+
+        // version.h and config.h of dependency were configured in its own dirs
+        let dep_mappings = clang::parser::SourceMappings {
+            header_include_by_sources: BTreeMap::from([(
+                String::from("/work/dep/src/lib.hpp"),
+                BTreeSet::from([String::from("/work/dep/src/lib.cpp")]),
+            )]),
+            ..Default::default()
+        };
+        let dep = render(
+            &ScanOptions {
+                project: String::from("dep"),
+                project_dir: String::from("/work/dep"),
+                target_dir: String::from("/work/app/.asc/source_deps/dep/target/dep"),
+                source_dir: String::from("/work/dep/src"),
+                entry_point_source: String::from("/work/dep/src/lib.cpp"),
+                static_lib: true,
+                cmake_minimum_version: String::from("3.20"),
+                ..Default::default()
+            },
+            &dep_mappings,
+            true,
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+        );
+        assert!(dep.contains(
+            "configure_file(${CMAKE_CURRENT_SOURCE_DIR}/version.h.in ${CMAKE_CURRENT_BINARY_DIR}/version.h @ONLY)"
+        ));
+        assert!(dep.contains(
+            "configure_file(${CMAKE_CURRENT_SOURCE_DIR}/config.h.cm ${CMAKE_CURRENT_BINARY_DIR}/config.h)"
+        ));
+        assert!(!dep.contains("${CMAKE_SOURCE_DIR}"));
+        // sources out of the dir of generated files were kept absolute
+        assert!(dep.contains("\"/work/dep/src/lib.cpp\""));
+        assert!(dep.contains("add_library("));
+
+        // dependents link libs of dependency and include its source dir
+        let app = render(
+            &ScanOptions {
+                project: String::from("app"),
+                project_dir: String::from("/work/app"),
+                target_dir: String::from("/work/app/target/app"),
+                source_dir: String::from("/work/app/src"),
+                entry_point_source: String::from("/work/app/src/main.cpp"),
+                cmake_minimum_version: String::from("3.20"),
+                ..Default::default()
+            },
+            &clang::parser::SourceMappings::default(),
+            true,
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            &BTreeMap::from([(String::from("dep"), String::from("/work/dep/src"))]),
+            &BTreeMap::new(),
+        );
+        assert!(app.contains("list(APPEND LINK_LIBS dep)"));
+        assert!(app.contains("    /work/dep/src\n"));
+        assert!(app.contains("    /work/app/src\n"));
+        assert!(app.contains("\"../../src/main.cpp\""));
+        assert!(app.contains("add_executable("));
+    }
+}
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     cli::{self, commands::VcpkgArgs},
-    config::system_paths,
+    config::{relative_paths, system_paths, vcpkg::registry_manifest::VcpkgConfiguration},
     util,
 };
 
@@ -39,8 +39,23 @@ pub fn gen(options: &cli::commands::scan::ScanOptions) {
         "-D CMAKE_TOOLCHAIN_FILE={}",
         system_paths::DataPath::vcpkg_scripts_build_systems_cmake_path(&vcpkg_clone_dir)
     );
-    let vcpkg_target_triplet = format!("-D VCPKG_TARGET_TRIPLET={}", default_vcpkg_triplet());
-    let vcpkg_host_triplet = format!("-D VCPKG_HOST_TRIPLET={}", default_vcpkg_triplet());
+    let select = |triplet: &String| {
+        if triplet.is_empty() {
+            default_vcpkg_triplet()
+        } else {
+            triplet.clone()
+        }
+    };
+    let vcpkg_target_triplet = format!(
+        "-D VCPKG_TARGET_TRIPLET={}",
+        select(&options.target_triplet)
+    );
+    let vcpkg_host_triplet = format!("-D VCPKG_HOST_TRIPLET={}", select(&options.host_triplet));
+    // always set to clear the cached value
+    let vcpkg_chainload_toolchain_file = format!(
+        "-D VCPKG_CHAINLOAD_TOOLCHAIN_FILE={}",
+        options.toolchain_file
+    );
     let mut args = vec![
         "-S",
         &options.project_dir,
@@ -49,12 +64,55 @@ pub fn gen(options: &cli::commands::scan::ScanOptions) {
         &cmake_toolchain_file,
         &vcpkg_target_triplet,
         &vcpkg_host_triplet,
+        &vcpkg_chainload_toolchain_file,
     ];
 
     if options.shared_lib {
         args.push("-D BUILD_SHARED_LIBS=1");
     }
 
+    // profile and triplet build directories share vcpkg installed packages with the default one
+    let cmake_build_type = format!("-D CMAKE_BUILD_TYPE={}", options.cmake_config);
+    let vcpkg_installed_dir = format!(
+        "-D VCPKG_INSTALLED_DIR={}/{}/{}",
+        util::fs::get_parent_dir(&options.project_dir),
+        relative_paths::ASC_TARGET_DIR_NAME,
+        relative_paths::VCPKG_INSTALLED_DIR_NAME
+    );
+    if !options.cmake_config.is_empty() {
+        args.push(&cmake_build_type);
+    }
+    if !options.cmake_config.is_empty() || !options.target_triplet.is_empty() {
+        args.push(&vcpkg_installed_dir);
+    }
+
+    // always set to clear the cached values
+    let sanitize = format!("-D ASC_SANITIZE={}", options.sanitizers.join(";"));
+    args.push(&sanitize);
+    let coverage = format!("-D ASC_COVERAGE={}", options.coverage);
+    args.push(&coverage);
+
+    // overlays in generated vcpkg-configuration.json, always set to clear the cached values
+    let vcpkg_conf_data = VcpkgConfiguration::load(
+        &format!(
+            "{}/{}",
+            options.project_dir,
+            relative_paths::VCPKG_CONFIGURATION_JSON_FILE_NAME
+        ),
+        true,
+    )
+    .unwrap_or_default();
+    let overlay_ports = format!(
+        "-D VCPKG_OVERLAY_PORTS={}",
+        vcpkg_conf_data.overlay_ports.join(";")
+    );
+    args.push(&overlay_ports);
+    let overlay_triplets = format!(
+        "-D VCPKG_OVERLAY_TRIPLETS={}",
+        vcpkg_conf_data.overlay_triplets.join(";")
+    );
+    args.push(&overlay_triplets);
+
     util::shell::run("cmake", &args, ".", false, false, false).unwrap();
 }
 
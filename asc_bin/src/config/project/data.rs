@@ -28,15 +28,48 @@ pub struct EntryConfig {
     pub std_c: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub std_cxx: String,
+    // libs export defines, include_dirs and link_libraries to dependents (PUBLIC)
+    // flags always stay PRIVATE, include_dirs are relative to the package dir
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defines: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cflags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cxxflags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ldflags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_dirs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_libraries: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Deserialize, Serialize)]
 pub struct DependencyConfig {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub version: String,
+    // inherit from [workspace.dependencies] (dep = { workspace = true }), features were unioned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<bool>,
+    // vcpkg registry of port, resolved by registry priority and packages patterns if empty
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub registry: String,
+    #[serde(default)]
     pub find_packages: BTreeSet<String>,
+    #[serde(default)]
     pub include_directories: BTreeSet<String>,
+    #[serde(default)]
     pub link_libraries: BTreeSet<String>,
+    #[serde(default)]
     pub features: BTreeSet<String>,
+    // local dir relative to the package dir (workspace member, asc or cmake project)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+    // git repository of asc or cmake project, checkout at rev
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub git: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub rev: String,
 }
 
 #[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Deserialize, Serialize)]
@@ -45,9 +78,53 @@ pub struct StdDependencyConfig {
     pub check: String,
 }
 
+// [vcpkg], paths were relative to the package dir
+#[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Deserialize, Serialize)]
+pub struct VcpkgConfig {
+    // dirs of patched ports, searched before registries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overlay_ports: Vec<String>,
+    // dirs of custom triplet files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overlay_triplets: Vec<String>,
+    // default triplets of asc build and asc install, derived from host os and arch if empty
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub target_triplet: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub host_triplet: String,
+    // chainloaded by vcpkg toolchain for cross compilation
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub toolchain_file: String,
+}
+
+#[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ProfileConfig {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub inherits: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub opt_level: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lto: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defines: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cflags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cxxflags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ldflags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sanitize: Vec<String>,
+}
+
 #[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Deserialize, Serialize)]
 pub struct WorkSpaceConfig {
     pub members: BTreeSet<String>,
+    // shared by members, path dependencies were relative to the workspace dir
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, DependencyConfig>,
 }
 
 #[derive(
@@ -61,12 +138,77 @@ pub struct ProjectConfig {
     pub bins: BTreeSet<EntryConfig>,
     #[serde(rename = "lib", default, skip_serializing_if = "BTreeSet::is_empty")]
     pub libs: BTreeSet<EntryConfig>,
+    #[serde(
+        rename = "example",
+        default,
+        skip_serializing_if = "BTreeSet::is_empty"
+    )]
+    pub examples: BTreeSet<EntryConfig>,
+    #[serde(rename = "test", default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tests: BTreeSet<EntryConfig>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub features: BTreeMap<String, BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub dependencies: BTreeMap<String, DependencyConfig>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub std_dependencies: BTreeMap<String, StdDependencyConfig>,
+    #[serde(default, skip_serializing_if = "VcpkgConfig::is_empty")]
+    pub vcpkg: VcpkgConfig,
+    #[serde(
+        rename = "profile",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+
+    #[serde(skip)]
+    pub path: String,
+}
+
+// asc.lock, resolved versions and registry baselines of vcpkg dependencies
+#[derive(Debug, Default, Clone, Deserialize, Serialize, ConfigFile)]
+#[config_file_ext("toml")]
+pub struct LockConfig {
+    #[serde(rename = "registry", default)]
+    pub registries: BTreeMap<String, LockedRegistry>,
+    #[serde(rename = "package", default)]
+    pub packages: BTreeMap<String, LockedPackage>,
+
+    #[serde(skip)]
+    pub path: String,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct LockedRegistry {
+    pub repository: String,
+    pub branch: String,
+    pub baseline: String,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct LockedPackage {
+    // version in asc.toml which was resolved
+    pub requirement: String,
+    pub version: String,
+    pub port_version: u32,
+    pub registry: String,
+    pub baseline: String,
+    #[serde(default)]
+    pub features: BTreeSet<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, ConfigFile)]
+#[config_file_ext("toml")]
+pub struct ScannedFiles {
+    pub files: Vec<String>,
+
+    // include name as spelled -> sources, resolved outside project or unresolved
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub third_party_includes: BTreeMap<String, BTreeSet<String>>,
+
+    // #include <...> of third_party_includes
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub third_party_angled_includes: BTreeMap<String, BTreeSet<String>>,
 
     #[serde(skip)]
     pub path: String,
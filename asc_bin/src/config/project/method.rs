@@ -1,6 +1,11 @@
 #[allow(unused_imports)]
-use super::data::{DependencyConfig, EntryConfig, PackageConfig, ProjectConfig, WorkSpaceConfig};
+use super::data::{
+    DependencyConfig, EntryConfig, LockConfig, LockedPackage, PackageConfig, ProfileConfig,
+    ProjectConfig, VcpkgConfig, WorkSpaceConfig,
+};
+use crate::cli::commands::ConfigType;
 use crate::config::relative_paths::{ASC_TARGET_DIR_NAME, ASC_TOML_FILE_NAME};
+use crate::vcpkg::version::{PortVersion, VersionReq};
 use crate::{config::relative_paths, errors::ErrorTag, util};
 
 #[allow(unused_imports)]
@@ -39,6 +44,12 @@ impl ProjectConfig {
             if !self.libs.is_empty() {
                 errors.push("libs");
             }
+            if !self.examples.is_empty() {
+                errors.push("examples");
+            }
+            if !self.tests.is_empty() {
+                errors.push("tests");
+            }
             if !self.dependencies.is_empty() {
                 errors.push("dependencies");
             }
@@ -60,6 +71,114 @@ impl ProjectConfig {
         return true;
     }
 
+    pub fn get_examples(&self, package_dir: &str) -> BTreeSet<EntryConfig> {
+        return self.discover_entries(
+            &self.examples,
+            relative_paths::EXAMPLES_DIR_NAME,
+            package_dir,
+            "example",
+            &BTreeSet::new(),
+        );
+    }
+
+    pub fn get_tests(&self, package_dir: &str) -> BTreeSet<EntryConfig> {
+        // tests share cmake target names with examples too
+        let examples = self.get_examples(package_dir);
+        return self.discover_entries(
+            &self.tests,
+            relative_paths::TESTS_DIR_NAME,
+            package_dir,
+            "test",
+            &examples,
+        );
+    }
+
+    fn discover_entries(
+        &self,
+        explicit_entries: &BTreeSet<EntryConfig>,
+        dir_name: &str,
+        package_dir: &str,
+        kind: &str,
+        others: &BTreeSet<EntryConfig>,
+    ) -> BTreeSet<EntryConfig> {
+        // explicit [[example]], [[test]] entries
+        let mut entries = explicit_entries.clone();
+
+        // auto discover <dir_name>/*.c, <dir_name>/*.cpp
+        if let Ok(dir_entries) = std::fs::read_dir(&format!("{package_dir}/{dir_name}")) {
+            let mut paths = dir_entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .collect::<Vec<std::path::PathBuf>>();
+            paths.sort();
+            for path in paths {
+                if !path.is_file() || !path.extension().is_some_and(|ext| util::fs::is_source(ext))
+                {
+                    continue;
+                }
+                let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+                if entries.iter().any(|e| e.name == name) {
+                    continue;
+                }
+                entries.insert(EntryConfig {
+                    name: name,
+                    source_dir: dir_name.to_string(),
+                    source_file: path.file_name().unwrap().to_str().unwrap().to_string(),
+                    ..Default::default()
+                });
+            }
+        }
+
+        // entries share cmake target names with bins and libs
+        entries.retain(|e| {
+            let conflict = self
+                .bins
+                .iter()
+                .chain(self.libs.iter())
+                .chain(others.iter())
+                .any(|t| t.name == e.name);
+            if conflict {
+                tracing::error!(
+                    func = "entries.retain",
+                    error_tag = ErrorTag::InvalidProjectPackageError.as_ref(),
+                    error_str = format!("{kind} {} conflicts with other target name", e.name),
+                );
+            }
+            !conflict
+        });
+
+        return entries;
+    }
+
+    pub fn flatten_examples(&self) -> BTreeSet<EntryConfig> {
+        return self.flatten_entries(|conf, dir| conf.get_examples(dir));
+    }
+
+    pub fn flatten_tests(&self) -> BTreeSet<EntryConfig> {
+        return self.flatten_entries(|conf, dir| conf.get_tests(dir));
+    }
+
+    fn flatten_entries<F>(&self, get_entries: F) -> BTreeSet<EntryConfig>
+    where
+        F: Fn(&ProjectConfig, &str) -> BTreeSet<EntryConfig>,
+    {
+        let cwd = util::fs::get_cwd();
+        match &self.workspace {
+            None => get_entries(self, &cwd),
+            Some(workspace) => {
+                let mut entries = BTreeSet::new();
+                for member in &workspace.members {
+                    if let Some(member_conf) =
+                        Self::load(&format!("{cwd}/{member}/{}", ASC_TOML_FILE_NAME), false)
+                    {
+                        entries.extend(get_entries(&member_conf, &format!("{cwd}/{member}")));
+                    }
+                }
+                entries
+            }
+        }
+    }
+
     pub fn is_project_inited(ignore_error: bool) -> bool {
         if util::fs::is_file_exists(ASC_TOML_FILE_NAME) {
             if ignore_error {
@@ -116,6 +235,191 @@ impl ProjectConfig {
     }
 }
 
+impl ProfileConfig {
+    // merge inherits chain down to a built-in profile (debug, release, relwithdebinfo, minsizerel)
+    // the resolved profile's inherits is the built-in profile name
+    pub fn resolve(
+        profiles: &BTreeMap<String, ProfileConfig>,
+        name: &str,
+    ) -> Option<ProfileConfig> {
+        let mut chain = vec![];
+        let mut current = name.to_string();
+        loop {
+            if chain.contains(&current) {
+                tracing::error!(
+                    func = "ProfileConfig::resolve",
+                    error_tag = ErrorTag::InvalidProjectError.as_ref(),
+                    error_str = format!(
+                        "profile inherits cycle {} -> {}",
+                        chain.join(" -> "),
+                        current
+                    ),
+                );
+                return None;
+            }
+            chain.push(current.clone());
+
+            if let Some(config_type) = ConfigType::from_profile(&current) {
+                let mut resolved = ProfileConfig {
+                    inherits: config_type.profile_name(),
+                    ..Default::default()
+                };
+                for n in chain.iter().rev() {
+                    if let Some(p) = profiles.get(n) {
+                        resolved = resolved.merge(p);
+                    }
+                }
+                return Some(resolved);
+            }
+
+            match profiles.get(&current) {
+                None => {
+                    tracing::error!(
+                        func = "profiles.get",
+                        error_tag = ErrorTag::InvalidProjectError.as_ref(),
+                        error_str = format!("profile {current} was not found"),
+                        profiles = profiles
+                            .keys()
+                            .map(|s| s.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(", "),
+                    );
+                    return None;
+                }
+                Some(p) => {
+                    current = if p.inherits.is_empty() {
+                        ConfigType::Debug.profile_name()
+                    } else {
+                        p.inherits.clone()
+                    };
+                }
+            }
+        }
+    }
+
+    fn merge(&self, child: &ProfileConfig) -> ProfileConfig {
+        ProfileConfig {
+            inherits: self.inherits.clone(),
+            opt_level: if child.opt_level.is_empty() {
+                self.opt_level.clone()
+            } else {
+                child.opt_level.clone()
+            },
+            debug: child.debug.or(self.debug),
+            lto: child.lto.or(self.lto),
+            defines: [self.defines.clone(), child.defines.clone()].concat(),
+            cflags: [self.cflags.clone(), child.cflags.clone()].concat(),
+            cxxflags: [self.cxxflags.clone(), child.cxxflags.clone()].concat(),
+            ldflags: [self.ldflags.clone(), child.ldflags.clone()].concat(),
+            sanitize: [self.sanitize.clone(), child.sanitize.clone()].concat(),
+        }
+    }
+
+    // resolve every configured profile, unresolvable ones were skipped with errors
+    pub fn resolve_all(
+        profiles: &BTreeMap<String, ProfileConfig>,
+    ) -> BTreeMap<String, ProfileConfig> {
+        let mut resolved = BTreeMap::new();
+        for name in profiles.keys() {
+            if let Some(p) = Self::resolve(profiles, name) {
+                resolved.insert(name.clone(), p);
+            }
+        }
+        return resolved;
+    }
+
+    pub fn is_builtin(name: &str) -> bool {
+        ConfigType::from_profile(name).is_some()
+    }
+
+    // cmake configuration of profile, custom profiles were added as new configurations
+    pub fn cmake_config(name: &str) -> String {
+        match ConfigType::from_profile(name) {
+            Some(c) => c.as_ref().to_string(),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl DependencyConfig {
+    pub fn is_path(&self) -> bool {
+        !self.path.is_empty()
+    }
+
+    pub fn is_git(&self) -> bool {
+        !self.git.is_empty()
+    }
+
+    // built from source instead of vcpkg ports
+    pub fn is_source(&self) -> bool {
+        self.is_path() || self.is_git()
+    }
+
+    pub fn is_workspace(&self) -> bool {
+        self.workspace == Some(true)
+    }
+
+    // same vcpkg port requested by another workspace member
+    // exact versions must be equal or matched by the range, overlapped ranges were intersected
+    pub fn merge(&mut self, other: &DependencyConfig) -> bool {
+        // built per member
+        if self.is_source() || other.is_source() {
+            return true;
+        }
+
+        if self.version != other.version {
+            if self.version.is_empty() {
+                self.version = other.version.clone();
+            } else if !other.version.is_empty() {
+                let (left, right) = match (
+                    VersionReq::parse(&self.version),
+                    VersionReq::parse(&other.version),
+                ) {
+                    (Some(l), Some(r)) => (l, r),
+                    _ => return false,
+                };
+                let exact = |text: &str| PortVersion::parse(text.trim_start_matches('='));
+                if left.is_exact() && right.is_exact() {
+                    // =11.0.2 and 11.0.2 were the same version
+                    if !left.matches(&exact(&other.version)) {
+                        return false;
+                    }
+                } else if left.is_exact() {
+                    if !right.matches(&exact(&self.version)) {
+                        return false;
+                    }
+                } else if right.is_exact() {
+                    if !left.matches(&exact(&other.version)) {
+                        return false;
+                    }
+                    self.version = other.version.clone();
+                } else if !left.intersects(&right) {
+                    return false;
+                } else {
+                    self.version = format!("{},{}", self.version, other.version);
+                }
+            }
+        }
+
+        if self.registry != other.registry {
+            if self.registry.is_empty() {
+                self.registry = other.registry.clone();
+            } else if !other.registry.is_empty() {
+                return false;
+            }
+        }
+
+        self.find_packages
+            .extend(other.find_packages.iter().cloned());
+        self.include_directories
+            .extend(other.include_directories.iter().cloned());
+        self.link_libraries
+            .extend(other.link_libraries.iter().cloned());
+        self.features.extend(other.features.iter().cloned());
+        return true;
+    }
+}
+
 impl WorkSpaceConfig {
     pub fn get_members(&self) -> String {
         self.members
@@ -124,11 +428,327 @@ impl WorkSpaceConfig {
             .collect::<Vec<&str>>()
             .join(", ")
     }
+
+    // resolve dep = { workspace = true } of member from [workspace.dependencies]
+    pub fn inherit_dependencies(&self, member: &str, conf: &mut ProjectConfig) -> bool {
+        let mut result = true;
+        for (name, dep) in conf.dependencies.iter_mut() {
+            if !dep.is_workspace() {
+                continue;
+            }
+            match self.dependencies.get(name) {
+                None => {
+                    tracing::error!(
+                        func = "self.dependencies.get",
+                        error_tag = ErrorTag::InvalidProjectWorkspaceError.as_ref(),
+                        error_str = format!("{name} of {member} was not in workspace.dependencies"),
+                    );
+                    result = false;
+                }
+                Some(shared) => {
+                    let mut inherited = shared.clone();
+                    // absolute paths were kept, relative ones were relative to workspace
+                    if inherited.is_path() && !std::path::Path::new(&shared.path).is_absolute() {
+                        inherited.path = format!(
+                            "{}{}",
+                            "../".repeat(member.split('/').filter(|c| !c.is_empty()).count()),
+                            shared.path
+                        );
+                    }
+                    inherited.find_packages.extend(dep.find_packages.clone());
+                    inherited
+                        .include_directories
+                        .extend(dep.include_directories.clone());
+                    inherited.link_libraries.extend(dep.link_libraries.clone());
+                    inherited.features.extend(dep.features.clone());
+                    *dep = inherited;
+                }
+            }
+        }
+        return result;
+    }
+
+    // merge dependencies of members, incompatible versions of same port were rejected
+    pub fn merge_dependencies(
+        dependencies: &mut BTreeMap<String, DependencyConfig>,
+        member: &str,
+        member_dependencies: &BTreeMap<String, DependencyConfig>,
+    ) -> bool {
+        let mut result = true;
+        for (name, dep) in member_dependencies {
+            match dependencies.get_mut(name) {
+                None => {
+                    dependencies.insert(name.clone(), dep.clone());
+                }
+                Some(merged) => {
+                    let version = merged.version.clone();
+                    if !merged.merge(dep) {
+                        tracing::error!(
+                            func = "merged.merge",
+                            error_tag = ErrorTag::InvalidProjectWorkspaceError.as_ref(),
+                            error_str = format!(
+                                "{member} requires {name} {}, which conflicts with {version}",
+                                dep.version
+                            ),
+                        );
+                        result = false;
+                    }
+                }
+            }
+        }
+        return result;
+    }
+
+    // load members and sort them by path dependencies, dependencies come first
+    // returns member name, member config and the members it depends on
+    pub fn load_sorted_members(
+        &self,
+        workspace_dir: &str,
+    ) -> Option<Vec<(String, ProjectConfig, BTreeSet<String>)>> {
+        let mut member_confs = BTreeMap::new();
+        let mut member_deps = BTreeMap::new();
+        for member in &self.members {
+            let mut conf = ProjectConfig::load(
+                &format!("{workspace_dir}/{member}/{ASC_TOML_FILE_NAME}"),
+                false,
+            )?;
+            if !self.inherit_dependencies(member, &mut conf) {
+                return None;
+            }
+
+            let mut deps = BTreeSet::new();
+            // other path dependencies were asc or cmake projects out of workspace
+            for dep in conf.dependencies.values() {
+                if let Some(m) = self.find_member(workspace_dir, member, dep) {
+                    deps.insert(m);
+                }
+            }
+
+            member_deps.insert(member.clone(), deps);
+            member_confs.insert(member.clone(), conf);
+        }
+
+        match Self::sort_members(&member_deps) {
+            Err(cycle) => {
+                tracing::error!(
+                    func = "Self::sort_members",
+                    error_tag = ErrorTag::InvalidProjectWorkspaceError.as_ref(),
+                    error_str = format!("path dependencies cycle {}", cycle.join(" -> ")),
+                );
+                return None;
+            }
+            Ok(sorted) => {
+                return Some(
+                    sorted
+                        .into_iter()
+                        .map(|m| {
+                            let conf = member_confs.remove(&m).unwrap();
+                            let deps = member_deps.remove(&m).unwrap();
+                            (m, conf, deps)
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    // workspace member which the path dependency of member points to
+    pub fn find_member(
+        &self,
+        workspace_dir: &str,
+        member: &str,
+        dep: &DependencyConfig,
+    ) -> Option<String> {
+        if !dep.is_path() {
+            return None;
+        }
+        let dep_dir = if std::path::Path::new(&dep.path).is_absolute() {
+            util::fs::normalize_path(&dep.path)
+        } else {
+            util::fs::normalize_path(&format!("{workspace_dir}/{member}/{}", dep.path))
+        };
+        return self
+            .members
+            .iter()
+            .find(|m| util::fs::normalize_path(&format!("{workspace_dir}/{m}")) == dep_dir)
+            .cloned();
+    }
+
+    // examples and tests of members were generated into one cmake project, their target names
+    // must not be used by other members
+    pub fn check_entry_names(
+        workspace_dir: &str,
+        members: &Vec<(String, ProjectConfig, BTreeSet<String>)>,
+    ) -> bool {
+        let mut targets = BTreeMap::<String, BTreeSet<String>>::new();
+        let mut discovered = BTreeMap::<String, BTreeSet<String>>::new();
+        for (member, conf, _) in members {
+            let package_dir = format!("{workspace_dir}/{member}");
+            let examples = conf.get_examples(&package_dir);
+            let tests = conf.get_tests(&package_dir);
+            for entry in examples.iter().chain(tests.iter()) {
+                discovered
+                    .entry(entry.name.clone())
+                    .or_default()
+                    .insert(member.clone());
+            }
+            for entry in conf
+                .bins
+                .iter()
+                .chain(conf.libs.iter())
+                .chain(examples.iter())
+                .chain(tests.iter())
+            {
+                targets
+                    .entry(entry.name.clone())
+                    .or_default()
+                    .insert(member.clone());
+            }
+        }
+
+        let mut result = true;
+        for name in discovered.keys() {
+            let owners = &targets[name];
+            if owners.len() > 1 {
+                tracing::error!(
+                    func = "WorkSpaceConfig::check_entry_names",
+                    error_tag = ErrorTag::InvalidProjectWorkspaceError.as_ref(),
+                    error_str = format!(
+                        "example or test {name} was defined by {}, rename it in asc.toml",
+                        owners.iter().cloned().collect::<Vec<String>>().join(", ")
+                    ),
+                );
+                result = false;
+            }
+        }
+        return result;
+    }
+
+    // depth first topological sort, returns the cycle if any
+    fn sort_members(deps: &BTreeMap<String, BTreeSet<String>>) -> Result<Vec<String>, Vec<String>> {
+        fn visit(
+            member: &String,
+            deps: &BTreeMap<String, BTreeSet<String>>,
+            visiting: &mut Vec<String>,
+            sorted: &mut Vec<String>,
+        ) -> Result<(), Vec<String>> {
+            if sorted.contains(member) {
+                return Ok(());
+            }
+            if let Some(pos) = visiting.iter().position(|m| m == member) {
+                let mut cycle = visiting[pos..].to_vec();
+                cycle.push(member.clone());
+                return Err(cycle);
+            }
+
+            visiting.push(member.clone());
+            for dep in deps.get(member).into_iter().flatten() {
+                visit(dep, deps, visiting, sorted)?;
+            }
+            visiting.pop();
+
+            sorted.push(member.clone());
+            return Ok(());
+        }
+
+        let mut sorted = vec![];
+        for member in deps.keys() {
+            visit(member, deps, &mut vec![], &mut sorted)?;
+        }
+        return Ok(sorted);
+    }
+}
+
+impl VcpkgConfig {
+    pub fn is_empty(&self) -> bool {
+        return self.overlay_ports.is_empty()
+            && self.overlay_triplets.is_empty()
+            && self.target_triplet.is_empty()
+            && self.host_triplet.is_empty()
+            && self.toolchain_file.is_empty();
+    }
+
+    // append overlays of package (workspace member) as absolute paths, duplicates were skipped
+    // triplets and toolchain file of the first package (workspace) were kept
+    pub fn extend(&mut self, package_dir: &str, other: &VcpkgConfig) {
+        let absolute = |dir: &String| {
+            if std::path::Path::new(dir).is_absolute() {
+                dir.replace(r"\", "/")
+            } else {
+                format!("{package_dir}/{}", dir.replace(r"\", "/"))
+            }
+        };
+        if self.target_triplet.is_empty() {
+            self.target_triplet = other.target_triplet.clone();
+        }
+        if self.host_triplet.is_empty() {
+            self.host_triplet = other.host_triplet.clone();
+        }
+        if self.toolchain_file.is_empty() && !other.toolchain_file.is_empty() {
+            self.toolchain_file = absolute(&other.toolchain_file);
+        }
+        for dir in other.overlay_ports.iter().map(absolute) {
+            if !self.overlay_ports.contains(&dir) {
+                self.overlay_ports.push(dir);
+            }
+        }
+        for dir in other.overlay_triplets.iter().map(absolute) {
+            if !self.overlay_triplets.contains(&dir) {
+                self.overlay_triplets.push(dir);
+            }
+        }
+    }
+}
+
+impl LockConfig {
+    // all vcpkg dependencies were locked with same version requirements and features,
+    // and no removed dependency was left in lock
+    pub fn is_up_to_date(&self, dependencies: &BTreeMap<String, DependencyConfig>) -> bool {
+        for name in self.packages.keys() {
+            match dependencies.get(name) {
+                Some(dep) if !dep.is_source() => {}
+                _ => return false,
+            }
+        }
+        for (name, dep) in dependencies {
+            if dep.is_source() {
+                continue;
+            }
+            match self.packages.get(name) {
+                None => return false,
+                Some(locked) => {
+                    if locked.requirement != dep.version
+                        || locked.features != dep.features
+                        || (!dep.registry.is_empty() && locked.registry != dep.registry)
+                        || !self.registries.contains_key(&locked.registry)
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+        return true;
+    }
+}
+
+impl LockedPackage {
+    pub fn format_version_text(&self) -> String {
+        if self.port_version == 0 {
+            self.version.clone()
+        } else {
+            format!("{}#{}", self.version, self.port_version)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        clang::parser::SourceMappings,
+        cli::commands::scan::{EntryKind, ScanArgs},
+        cmake,
+    };
 
     const TEXT_WORKSPACE: &str = r#"[workspace]
 members = [
@@ -219,6 +839,74 @@ default = [
         assert!(data.is_some());
     }
 
+    #[test]
+    fn test_entry_flags() {
+        let data = ProjectConfig::loads(
+            r#"[package]
+name = "a"
+version = "2024.10.21"
+edition = "2024"
+description = ""
+license = ""
+repository = ""
+branch = "main"
+
+[[lib]]
+name = "a"
+source_dir = "src"
+source_file = "lib.cpp"
+shared = false
+defines = ["A_STATIC", "A_LEVEL=2"]
+cflags = ["-Wno-unused"]
+cxxflags = ["-fno-rtti"]
+ldflags = ["-Wl,--as-needed"]
+include_dirs = ["include", "/opt/x/include"]
+link_libraries = ["m"]
+"#,
+            false,
+        )
+        .unwrap();
+        assert!(data.validate());
+        let lib = data.libs.first().unwrap();
+        assert_eq!(lib.defines, vec!["A_STATIC", "A_LEVEL=2"]);
+        assert_eq!(lib.cflags, vec!["-Wno-unused"]);
+        assert_eq!(lib.cxxflags, vec!["-fno-rtti"]);
+        assert_eq!(lib.ldflags, vec!["-Wl,--as-needed"]);
+        assert_eq!(lib.include_dirs, vec!["include", "/opt/x/include"]);
+        assert_eq!(lib.link_libraries, vec!["m"]);
+
+        let options = ScanArgs {
+            cmake_minimum_version: String::from("3.20"),
+            suggest_deps: false,
+            skip_configure: true,
+            output_dir: String::new(),
+        }
+        .entry_options("/work/a", "/work/a", "/work/a", EntryKind::Lib, lib);
+        let text = cmake::lists::render(
+            &options,
+            &SourceMappings::default(),
+            true,
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+            &BTreeMap::new(),
+        );
+        // libs export defines, include dirs and link libraries, flags stay private
+        assert!(text.contains(
+            "target_compile_definitions(\n    ${PROJECT_NAME}\n    PUBLIC\n    \"A_STATIC\"\n    \"A_LEVEL=2\"\n)"
+        ));
+        assert!(text.contains(
+            "target_compile_options(\n    ${PROJECT_NAME}\n    PRIVATE\n    \"$<$<COMPILE_LANGUAGE:C>:-Wno-unused>\"\n    \"$<$<COMPILE_LANGUAGE:CXX>:-fno-rtti>\"\n)"
+        ));
+        assert!(text.contains(
+            "target_link_options(\n    ${PROJECT_NAME}\n    PRIVATE\n    \"-Wl,--as-needed\"\n)"
+        ));
+        assert!(text.contains(
+            "    PUBLIC\n    \"$<BUILD_INTERFACE:/work/a/include>\"\n    \"$<BUILD_INTERFACE:/opt/x/include>\"\n)"
+        ));
+        assert!(text.contains("target_link_libraries(\n    ${PROJECT_NAME}\n    PUBLIC\n    m\n)"));
+    }
+
     #[test]
     fn test_dumps() {
         let mut data = ProjectConfig::default();
@@ -241,6 +929,7 @@ default = [
             shared: None,
             std_c: String::new(),
             std_cxx: String::new(),
+            ..Default::default()
         });
 
         data.workspace = Some(WorkSpaceConfig {
@@ -255,6 +944,7 @@ default = [
                 String::from("clap_mangen"),
             ]
             .into(),
+            dependencies: BTreeMap::new(),
         });
 
         let mut features = BTreeMap::new();
@@ -287,6 +977,11 @@ default = [
                 include_directories: BTreeSet::new(),
                 link_libraries: BTreeSet::new(),
                 features: BTreeSet::new(),
+                path: String::new(),
+                git: String::new(),
+                rev: String::new(),
+                workspace: None,
+                registry: String::new(),
             },
         );
         dependencies.insert(
@@ -297,6 +992,11 @@ default = [
                 include_directories: BTreeSet::new(),
                 link_libraries: BTreeSet::new(),
                 features: [String::from("derive")].into(),
+                path: String::new(),
+                git: String::new(),
+                rev: String::new(),
+                workspace: None,
+                registry: String::new(),
             },
         );
         dependencies.insert(
@@ -313,6 +1013,11 @@ default = [
                     String::from("json"),
                 ]
                 .into(),
+                path: String::new(),
+                git: String::new(),
+                rev: String::new(),
+                workspace: None,
+                registry: String::new(),
             },
         );
 
@@ -338,9 +1043,397 @@ default = [
         assert_eq!(data.unwrap().validate(), true);
     }
 
+    #[test]
+    fn test_explicit_examples() {
+        let text = format!(
+            "{TEXT_PACKAGE}
+[[example]]
+name = \"a\"
+source_dir = \"examples\"
+source_file = \"a.cpp\"
+
+[[example]]
+name = \"demo\"
+source_dir = \"examples\"
+source_file = \"demo.cpp\"
+"
+        );
+        let data = ProjectConfig::loads(&text, false).unwrap();
+        assert_eq!(data.examples.len(), 2);
+
+        // "a" conflicts with [[bin]] a
+        let examples = data.get_examples("directory_not_exists");
+        assert_eq!(
+            examples
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["demo"]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_workspace_entries() {
+        let entry = |kind: &str, name: &str| {
+            format!(
+                "[[{kind}]]\nname = \"{name}\"\nsource_dir = \"{kind}s\"\nsource_file = \"{name}.cpp\"\n"
+            )
+        };
+        let member = |text: String| {
+            let mut conf = ProjectConfig::loads(&text, false).unwrap();
+            conf.bins.clear();
+            conf
+        };
+        let a = member(format!("{TEXT_PACKAGE}{}", entry("example", "demo")));
+        let b = member(format!("{TEXT_PACKAGE}{}", entry("test", "unit")));
+        let mut members = vec![
+            (String::from("a"), a, BTreeSet::new()),
+            (String::from("b"), b, BTreeSet::new()),
+        ];
+        assert!(WorkSpaceConfig::check_entry_names(
+            "directory_not_exists",
+            &members
+        ));
+
+        // test demo of b conflicts with example demo of a
+        let b = member(format!("{TEXT_PACKAGE}{}", entry("test", "demo")));
+        members[1].1 = b;
+        assert!(!WorkSpaceConfig::check_entry_names(
+            "directory_not_exists",
+            &members
+        ));
+
+        // example demo of a conflicts with bin demo of b
+        let mut b = member(TEXT_PACKAGE.to_string());
+        b.bins.insert(EntryConfig {
+            name: String::from("demo"),
+            ..Default::default()
+        });
+        members[1].1 = b;
+        assert!(!WorkSpaceConfig::check_entry_names(
+            "directory_not_exists",
+            &members
+        ));
+    }
+
+    #[test]
+    fn test_profiles_inherits() {
+        let text = format!(
+            "{TEXT_PACKAGE}
+[profile.release]
+lto = true
+
+[profile.asan]
+inherits = \"release\"
+debug = true
+cflags = [\"-fsanitize=address\"]
+
+[profile.asan-o0]
+inherits = \"asan\"
+opt_level = \"0\"
+
+[profile.loop-a]
+inherits = \"loop-b\"
+
+[profile.loop-b]
+inherits = \"loop-a\"
+"
+        );
+        let data = ProjectConfig::loads(&text, false).unwrap();
+
+        let asan = ProfileConfig::resolve(&data.profiles, "asan-o0").unwrap();
+        assert_eq!(asan.inherits, "release");
+        assert_eq!(asan.opt_level, "0");
+        assert_eq!(asan.debug, Some(true));
+        assert_eq!(asan.lto, Some(true));
+        assert_eq!(asan.cflags, vec!["-fsanitize=address"]);
+        assert_eq!(ProfileConfig::cmake_config("asan-o0"), "asan-o0");
+        assert_eq!(
+            ProfileConfig::cmake_config("relwithdebinfo"),
+            "RelWithDebInfo"
+        );
+
+        assert!(ProfileConfig::resolve(&data.profiles, "loop-a").is_none());
+        assert!(ProfileConfig::resolve(&data.profiles, "not-exists").is_none());
+        assert_eq!(
+            ProfileConfig::resolve(&data.profiles, "minsizerel")
+                .unwrap()
+                .inherits,
+            "minsizerel"
+        );
+    }
+
+    #[test]
+    fn test_sort_members() {
+        let deps = BTreeMap::from([
+            (String::from("a"), BTreeSet::from([String::from("c")])),
+            (String::from("b"), BTreeSet::new()),
+            (String::from("c"), BTreeSet::from([String::from("b")])),
+        ]);
+        assert_eq!(
+            WorkSpaceConfig::sort_members(&deps),
+            Ok(vec![
+                String::from("b"),
+                String::from("c"),
+                String::from("a")
+            ])
+        );
+
+        let deps = BTreeMap::from([
+            (String::from("a"), BTreeSet::from([String::from("b")])),
+            (String::from("b"), BTreeSet::from([String::from("a")])),
+        ]);
+        assert_eq!(
+            WorkSpaceConfig::sort_members(&deps),
+            Err(vec![
+                String::from("a"),
+                String::from("b"),
+                String::from("a")
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lock_is_up_to_date() {
+        let lock = LockConfig::loads(
+            r#"[registry.vcpkg]
+repository = "https://github.com/microsoft/vcpkg"
+branch = "master"
+baseline = "c82f74667287d3dc386bce81e44964370c91a289"
+
+[package.fmt]
+requirement = "11.0.2"
+version = "11.0.2"
+port_version = 1
+registry = "vcpkg"
+baseline = "c82f74667287d3dc386bce81e44964370c91a289"
+"#,
+            false,
+        )
+        .unwrap();
+        assert_eq!(lock.packages["fmt"].format_version_text(), "11.0.2#1");
+
+        let mut deps = BTreeMap::from([(
+            String::from("fmt"),
+            DependencyConfig {
+                version: String::from("11.0.2"),
+                ..Default::default()
+            },
+        )]);
+        assert!(lock.is_up_to_date(&deps));
+
+        deps.get_mut("fmt")
+            .unwrap()
+            .features
+            .insert(String::from("x"));
+        assert!(!lock.is_up_to_date(&deps));
+
+        // fmt was removed from asc.toml
+        deps.clear();
+        assert!(!lock.is_up_to_date(&deps));
+    }
+
+    #[test]
+    fn test_workspace_dependencies() {
+        let data = ProjectConfig::loads(
+            r#"[workspace]
+members = ["a", "libs/b"]
+
+[workspace.dependencies]
+fmt = { version = "^11.0" }
+c = { path = "libs/c" }
+"#,
+            false,
+        )
+        .unwrap();
+        assert!(data.validate());
+        let workspace = data.workspace.unwrap();
+
+        let mut member = ProjectConfig::loads(
+            r#"[package]
+name = "b"
+version = "2024.12.11"
+edition = "2024"
+description = ""
+license = ""
+repository = ""
+branch = ""
+
+[dependencies]
+fmt = { workspace = true, features = ["x"] }
+c = { workspace = true }
+"#,
+            false,
+        )
+        .unwrap();
+        assert!(workspace.inherit_dependencies("libs/b", &mut member));
+        assert_eq!(member.dependencies["fmt"].version, "^11.0");
+        assert_eq!(member.dependencies["c"].path, "../../libs/c");
+
+        let workspace_dir =
+            util::fs::normalize_path(&std::env::temp_dir().join("ws").to_string_lossy());
+        let relative = DependencyConfig {
+            path: String::from("../a"),
+            ..Default::default()
+        };
+        let absolute = DependencyConfig {
+            path: format!("{workspace_dir}/a"),
+            ..Default::default()
+        };
+        let outside = DependencyConfig {
+            path: format!("{workspace_dir}/../a"),
+            ..Default::default()
+        };
+        assert_eq!(
+            workspace.find_member(&workspace_dir, "libs", &relative),
+            Some(String::from("a"))
+        );
+        assert_eq!(
+            workspace.find_member(&workspace_dir, "libs/b", &absolute),
+            Some(String::from("a"))
+        );
+        assert_eq!(
+            workspace.find_member(&workspace_dir, "libs/b", &outside),
+            None
+        );
+
+        let mut shared = workspace.clone();
+        shared
+            .dependencies
+            .insert(String::from("c"), absolute.clone());
+        let mut member = ProjectConfig {
+            dependencies: BTreeMap::from([(
+                String::from("c"),
+                DependencyConfig {
+                    workspace: Some(true),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        assert!(shared.inherit_dependencies("libs/b", &mut member));
+        assert_eq!(member.dependencies["c"].path, absolute.path);
+
+        let mut dependencies = BTreeMap::new();
+        assert!(WorkSpaceConfig::merge_dependencies(
+            &mut dependencies,
+            "libs/b",
+            &member.dependencies
+        ));
+        let exact = BTreeMap::from([(
+            String::from("fmt"),
+            DependencyConfig {
+                version: String::from("11.0.2"),
+                ..Default::default()
+            },
+        )]);
+        assert!(WorkSpaceConfig::merge_dependencies(
+            &mut dependencies,
+            "a",
+            &exact
+        ));
+        assert_eq!(dependencies["fmt"].version, "11.0.2");
+        let conflict = BTreeMap::from([(
+            String::from("fmt"),
+            DependencyConfig {
+                version: String::from("10.2.1"),
+                ..Default::default()
+            },
+        )]);
+        assert!(!WorkSpaceConfig::merge_dependencies(
+            &mut dependencies,
+            "a",
+            &conflict
+        ));
+
+        let dep = |version: &str| DependencyConfig {
+            version: String::from(version),
+            ..Default::default()
+        };
+        // same exact version spelled differently
+        let mut merged = dep("=11.0.2");
+        assert!(merged.merge(&dep("11.0.2")));
+        assert_eq!(merged.version, "=11.0.2");
+        // overlapped ranges were intersected, disjoint ranges were rejected
+        let mut merged = dep("^1.14");
+        assert!(merged.merge(&dep(">=1.15,<3")));
+        assert_eq!(merged.version, "^1.14,>=1.15,<3");
+        assert!(!merged.merge(&dep("^2")));
+        assert!(!dep("^1").merge(&dep("^2")));
+    }
+
     #[test]
     fn test_valid_package() {
         let data = ProjectConfig::loads(TEXT_PACKAGE, false);
         assert_eq!(data.unwrap().validate(), true);
     }
+
+    #[test]
+    fn test_vcpkg_overlays() {
+        let conf = ProjectConfig::loads(
+            r#"[package]
+name = "a"
+version = "2024.1.1"
+edition = "2024"
+description = ""
+license = "LGPL-3.0-or-later"
+repository = ""
+branch = "main"
+
+[vcpkg]
+overlay_ports = ["overlay_ports", "/opt/ports"]
+overlay_triplets = ["triplets"]
+target_triplet = "arm64-linux"
+toolchain_file = "cmake/aarch64.cmake"
+"#,
+            false,
+        )
+        .unwrap();
+
+        let mut vcpkg = VcpkgConfig::default();
+        vcpkg.extend("/work/a", &conf.vcpkg);
+        vcpkg.extend("/work/a", &conf.vcpkg);
+        assert_eq!(
+            vcpkg.overlay_ports,
+            vec!["/work/a/overlay_ports", "/opt/ports"]
+        );
+        assert_eq!(vcpkg.overlay_triplets, vec!["/work/a/triplets"]);
+        assert_eq!(vcpkg.target_triplet, "arm64-linux");
+        assert!(vcpkg.host_triplet.is_empty());
+        assert_eq!(vcpkg.toolchain_file, "/work/a/cmake/aarch64.cmake");
+        assert_eq!(
+            relative_paths::triplet_target_dir("target/profiles/minsizerel", "arm64-linux"),
+            "target/arm64-linux/profiles/minsizerel"
+        );
+        assert_eq!(
+            relative_paths::triplet_dir_name("x64-linux", "x64-linux", "", "x64-linux"),
+            None
+        );
+        assert_eq!(
+            relative_paths::triplet_dir_name("arm64-linux", "x64-linux", "", "x64-linux"),
+            Some(String::from("arm64-linux"))
+        );
+        assert_eq!(
+            relative_paths::triplet_dir_name("x64-linux", "x86-linux", "", "x64-linux"),
+            Some(String::from("x64-linux_host-x86-linux"))
+        );
+        let toolchain = relative_paths::triplet_dir_name(
+            "x64-linux",
+            "x64-linux",
+            "/work/a/cmake/aarch64.cmake",
+            "x64-linux",
+        )
+        .unwrap();
+        assert!(toolchain.starts_with("x64-linux_toolchain-"));
+        assert_ne!(
+            Some(toolchain),
+            relative_paths::triplet_dir_name(
+                "x64-linux",
+                "x64-linux",
+                "/work/a/cmake/x64.cmake",
+                "x64-linux"
+            )
+        );
+        assert!(ProjectConfig::default().vcpkg.is_empty());
+    }
 }
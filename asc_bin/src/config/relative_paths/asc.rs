@@ -1,16 +1,28 @@
+use sha1::{Digest, Sha1};
+
 use crate::{config::project::InstalledFiles, util};
 
 use super::{CMAKE_INSTALL_INCLUDE_DIR_NAME, CMAKE_INSTALL_SHARE_DIR_NAME};
 
 pub static ASC_TOML_FILE_NAME: &str = "asc.toml";
+pub static ASC_LOCK_FILE_NAME: &str = "asc.lock";
 pub static ASC_EDITION: &str = "2024";
 pub static ASC_PROJECT_DIR_NAME: &str = ".asc";
 pub static ASC_PROJECT_INSTALLED_FILES_TOML_PATH: &str = ".asc/installed_files.toml";
 pub static ASC_TARGET_DIR_NAME: &str = "target";
 pub static ASC_INSTALLED_DIR_NAME: &str = "installed";
 pub static ASC_TARGET_INSTALLED_DIR: &str = "target/installed";
+pub static ASC_TARGET_PROFILES_DIR_NAME: &str = "profiles";
+pub static ASC_TARGET_SANITIZE_DIR_NAME: &str = "sanitize";
+pub static ASC_TARGET_COVERAGE_DIR: &str = "target/coverage";
+pub static ASC_GIT_DEPENDENCIES_DIR_NAME: &str = "git_deps";
+pub static ASC_SOURCE_DEPENDENCIES_DIR_NAME: &str = "source_deps";
+pub static ASC_OVERLAY_PORTS_DIR_NAME: &str = "overlay_ports";
 
 pub static SRC_DIR_NAME: &str = "src";
+pub static EXAMPLES_DIR_NAME: &str = "examples";
+pub static EXAMPLE_CPP_FILE_NAME: &str = "example.cpp";
+pub static TESTS_DIR_NAME: &str = "tests";
 pub static MAIN_CPP_FILE_NAME: &str = "main.cpp";
 pub static LIB_HPP_FILE_NAME: &str = "lib.hpp";
 pub static LIB_CPP_FILE_NAME: &str = "lib.cpp";
@@ -68,6 +80,59 @@ pub fn uninstall_installed_files() -> bool {
     return has_error;
 }
 
+// debug and release share the default build directory
+// sanitizer builds never mix objects with other builds
+pub fn profile_target_dir(profile: &str, sanitizers: &Vec<String>) -> String {
+    if !sanitizers.is_empty() {
+        format!(
+            "{ASC_TARGET_DIR_NAME}/{ASC_TARGET_SANITIZE_DIR_NAME}/{profile}-{}",
+            sanitizers.join("-")
+        )
+    } else if profile == "debug" || profile == "release" {
+        ASC_TARGET_DIR_NAME.to_string()
+    } else {
+        format!("{ASC_TARGET_DIR_NAME}/{ASC_TARGET_PROFILES_DIR_NAME}/{profile}")
+    }
+}
+
+// every non-default triplet and toolchain file gets its own build directory
+// (arm64-linux, arm64-linux_host-x64-windows, x64-linux_toolchain-<hash>), None for host builds
+pub fn triplet_dir_name(
+    target_triplet: &str,
+    host_triplet: &str,
+    toolchain_file: &str,
+    default_triplet: &str,
+) -> Option<String> {
+    if target_triplet == default_triplet
+        && host_triplet == default_triplet
+        && toolchain_file.is_empty()
+    {
+        return None;
+    }
+
+    let mut name = target_triplet.to_string();
+    if host_triplet != default_triplet {
+        name.push_str(&format!("_host-{host_triplet}"));
+    }
+    if !toolchain_file.is_empty() {
+        let mut hasher = Sha1::new();
+        hasher.update(toolchain_file.as_bytes());
+        let hash = format!("{:x}", hasher.finalize());
+        name.push_str(&format!("_toolchain-{}", &hash[..8]));
+    }
+    return Some(name);
+}
+
+// cross builds never mix objects with host builds (target/arm64-linux/profiles/minsizerel)
+pub fn triplet_target_dir(target_dir: &str, triplet: &str) -> String {
+    format!(
+        "{ASC_TARGET_DIR_NAME}/{triplet}{}",
+        target_dir
+            .strip_prefix(ASC_TARGET_DIR_NAME)
+            .unwrap_or_default()
+    )
+}
+
 pub fn vcpkg_installed_bin_dir_path(profile: &str, triplet: &str) -> String {
     format!(
         "{ASC_TARGET_DIR_NAME}/{VCPKG_INSTALLED_DIR_NAME}/{triplet}/{}{VCPKG_BIN_DIR_NAME}",
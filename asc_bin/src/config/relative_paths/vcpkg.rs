@@ -14,11 +14,15 @@ pub static VCPKG_CMAKE_FILE_NAME: &str = "vcpkg.cmake";
 pub static VCPKG_PORT_FILE_CMAKE_FILE_NAME: &str = "portfile.cmake";
 pub static VCPKG_BOOTSTRAP_SH_FILE_NAME: &str = "bootstrap-vcpkg.sh";
 pub static VCPKG_BOOTSTRAP_BAT_FILE_NAME: &str = "bootstrap-vcpkg.bat";
+// ports of filesystem registry were copied into snapshots/<port>/<version>_<port version> when published
+pub static VCPKG_SNAPSHOTS_DIR_NAME: &str = "snapshots";
 
 // vcpkg.index
 pub static VCPKG_INDEX_DIR_NAME: &str = "vcpkg.index";
 pub static VCPKG_SEARCH_INDEX_JSON_FILE_NAME: &str = "search_index.json";
 pub static VCPKG_TREE_INDEX_JSON_FILE_NAME: &str = "tree_index.json";
+pub static VCPKG_SEARCH_INDEX_BIN_FILE_NAME: &str = "search_index.bin";
+pub static VCPKG_TREE_INDEX_BIN_FILE_NAME: &str = "tree_index.bin";
 
 // vcpkg.downloads
 pub static VPCKG_DOWNLOADS_DIR_NAME: &str = "vcpkg.downloads";
@@ -1,9 +1,12 @@
+use sha1::{Digest, Sha1};
+
 use super::{build, APPLICATION, ORGANIZATION, QUALIFIER};
 
 use crate::config::relative_paths::{
     self, ASC_REGISTRY_DIR_NAME, VCPKG_BASELINE_JSON_FILE_NAME, VCPKG_BUILD_SYSTEMS_DIR_NAME,
     VCPKG_CMAKE_FILE_NAME, VCPKG_DIR_NAME, VCPKG_JSON_FILE_NAME, VCPKG_PORTS_DIR_NAME,
-    VCPKG_PORT_FILE_CMAKE_FILE_NAME, VCPKG_SCRIPTS_DIR_NAME, VCPKG_VERSIONS_DIR_NAME,
+    VCPKG_PORT_FILE_CMAKE_FILE_NAME, VCPKG_SCRIPTS_DIR_NAME, VCPKG_SNAPSHOTS_DIR_NAME,
+    VCPKG_VERSIONS_DIR_NAME,
 };
 
 pub struct DataPath {}
@@ -107,6 +110,24 @@ impl DataPath {
         )
     }
 
+    pub fn vcpkg_port_snapshot_dir_path(
+        vcpkg_clone_dir: &str,
+        port_name: &str,
+        version: &str,
+        port_version: u32,
+    ) -> String {
+        build(
+            vcpkg_clone_dir,
+            vec![
+                String::from(VCPKG_SNAPSHOTS_DIR_NAME),
+                port_name.to_string(),
+                format!("{version}_{port_version}"),
+            ],
+            false,
+            true,
+        )
+    }
+
     pub fn vcpkg_versions_baseline_json_path(vcpkg_clone_dir: &str) -> String {
         build(
             vcpkg_clone_dir,
@@ -165,6 +186,30 @@ impl DataPath {
         )
     }
 
+    pub fn vcpkg_search_index_bin(index_dir: &str, name: &str) -> String {
+        build(
+            index_dir,
+            vec![format!(
+                "{name}.{}",
+                relative_paths::VCPKG_SEARCH_INDEX_BIN_FILE_NAME,
+            )],
+            true,
+            false,
+        )
+    }
+
+    pub fn vcpkg_tree_index_bin(index_dir: &str, name: &str) -> String {
+        build(
+            index_dir,
+            vec![format!(
+                "{name}.{}",
+                relative_paths::VCPKG_TREE_INDEX_BIN_FILE_NAME,
+            )],
+            true,
+            false,
+        )
+    }
+
     pub fn vcpkg_default_downloads_dir() -> String {
         build(
             &Self::prefix(),
@@ -174,6 +219,22 @@ impl DataPath {
         )
     }
 
+    // same name from different urls or revs never share a clone, rev may contain '/'
+    pub fn git_dependency_clone_dir(name: &str, url: &str, rev: &str) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(format!("{url}#{rev}").as_bytes());
+        let hash = format!("{:x}", hasher.finalize());
+        build(
+            &Self::prefix(),
+            vec![
+                String::from(relative_paths::ASC_GIT_DEPENDENCIES_DIR_NAME),
+                format!("{name}-{}", &hash[..16]),
+            ],
+            false,
+            true,
+        )
+    }
+
     pub fn vcpkg_default_binary_cache_dir() -> String {
         build(
             &Self::prefix(),
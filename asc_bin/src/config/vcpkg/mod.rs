@@ -1,5 +1,5 @@
 pub mod port;
-// pub mod port_manifest;
+pub mod port_manifest;
 pub mod registry_manifest;
 pub mod versions_baseline;
 pub mod versions_port;
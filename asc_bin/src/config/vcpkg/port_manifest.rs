@@ -16,73 +16,154 @@ static HOMEPAGE_PREFIX: &str = "Homepage:";
 static DESCRIPTION_PREFIX: &str = "Description:";
 static SUPPORTS_PREFIX: &str = "Supports:";
 static DEFAULT_FEATURES_PREFIX: &str = "Default-Features:";
-static FEATURES_PREFIX: &str = "Features:";
+static FEATURE_PREFIX: &str = "Feature:";
 static BUILD_DEPENDS_PREFIX: &str = "Build-Depends:";
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, ConfigFile)]
 #[config_file_ext("json")]
-struct VcpkgPortManifest {
+#[serde(rename_all = "kebab-case")]
+pub struct VcpkgPortManifest {
     #[serde(skip)]
     path: String,
 
-    name: String,
+    pub name: String,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version_date: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version_semver: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version_string: Option<String>,
 
     #[serde(default)]
     pub port_version: u32,
 
-    #[serde(skip_serializing_if = "String::is_empty")]
-    homepage: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub homepage: String,
 
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    description: Vec<String>,
+    #[serde(
+        default,
+        deserialize_with = "string_or_vec",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub description: Vec<String>,
 
-    #[serde(skip_serializing_if = "String::is_empty")]
-    supports: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
 
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    default_features: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub supports: String,
 
-    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-    features: BTreeMap<String, VcpkgPortFeature>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_features: Vec<VcpkgPortDependency>,
 
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    dependencies: Vec<VcpkgPortDependency>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub features: BTreeMap<String, VcpkgPortFeature>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<VcpkgPortDependency>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
-struct VcpkgPortFeature {
-    description: String,
+pub struct VcpkgPortFeature {
+    #[serde(default, deserialize_with = "string_or_vec")]
+    pub description: Vec<String>,
 
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    dependencies: Vec<VcpkgPortDependency>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub supports: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<VcpkgPortDependency>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
-enum VcpkgPortDependency {
+pub enum VcpkgPortDependency {
     Simple(String),
     Complex(ComplexDependency),
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
-struct ComplexDependency {
-    name: String,
+#[serde(default, rename_all = "kebab-case")]
+pub struct ComplexDependency {
+    pub name: String,
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    features: Vec<String>,
-    default_features: bool,
-    platform: Option<String>,
+    pub features: Vec<VcpkgPortDependency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_features: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<bool>,
+    #[serde(rename = "version>=", skip_serializing_if = "Option::is_none")]
+    pub version_ge: Option<String>,
+}
+
+// description was string or array of strings
+fn string_or_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrVec {
+        String(String),
+        Vec(Vec<String>),
+    }
+
+    return match StringOrVec::deserialize(deserializer)? {
+        StringOrVec::String(s) => Ok(vec![s]),
+        StringOrVec::Vec(v) => Ok(v),
+    };
+}
+
+impl VcpkgPortDependency {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Simple(name) => name,
+            Self::Complex(dep) => &dep.name,
+        }
+    }
+
+    pub fn features(&self) -> Vec<String> {
+        match self {
+            Self::Simple(_) => vec![],
+            Self::Complex(dep) => dep.features.iter().map(|f| f.name().to_string()).collect(),
+        }
+    }
+
+    pub fn platform(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Complex(dep) => dep.platform.as_ref(),
+        }
+    }
+
+    pub fn default_features(&self) -> bool {
+        match self {
+            Self::Simple(_) => true,
+            Self::Complex(dep) => dep.default_features.unwrap_or(true),
+        }
+    }
+
+    pub fn is_host(&self) -> bool {
+        match self {
+            Self::Simple(_) => false,
+            Self::Complex(dep) => dep.host.unwrap_or(false),
+        }
+    }
+
+    pub fn version_ge(&self) -> Option<&String> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Complex(dep) => dep.version_ge.as_ref(),
+        }
+    }
 }
 
 impl VcpkgPortManifest {
@@ -99,115 +180,207 @@ impl VcpkgPortManifest {
             return None;
         }
 
-        let mut manifest = VcpkgPortManifest::default();
         let text = std::fs::read_to_string(path).unwrap();
-        for lines in text.split("\n\n\n") {
+        return Self::from_control_text(&text);
+    }
+
+    pub fn from_control_text(text: &str) -> Option<VcpkgPortManifest> {
+        let mut manifest = VcpkgPortManifest::default();
+        let text = text.replace("\r\n", "\n");
+        for lines in text.split("\n\n") {
+            let lines = lines.trim();
             // source section
             if lines.starts_with(SOURCE_PREFIX) {
                 let mut entry = "";
                 for line in lines.lines() {
                     if line.starts_with(SOURCE_PREFIX) {
                         entry = SOURCE_PREFIX;
-                        manifest.name = line.split_at(SOURCE_PREFIX.len()).1.trim().to_string();
+                        manifest.name = Self::value(line, SOURCE_PREFIX);
                     } else if line.starts_with(VERSION_PREFIX) {
                         entry = VERSION_PREFIX;
-                        manifest.version =
-                            Some(line.split_at(VERSION_PREFIX.len()).1.trim().to_string());
+                        manifest.version = Some(Self::value(line, VERSION_PREFIX));
                     } else if line.starts_with(VERSION_DATE_PREFIX) {
                         entry = VERSION_DATE_PREFIX;
-                        manifest.version_date =
-                            Some(line.split_at(VERSION_DATE_PREFIX.len()).1.trim().to_string());
+                        manifest.version_date = Some(Self::value(line, VERSION_DATE_PREFIX));
                     } else if line.starts_with(VERSION_SEMVER_PREFIX) {
                         entry = VERSION_SEMVER_PREFIX;
-                        manifest.version_semver = Some(
-                            line.split_at(VERSION_SEMVER_PREFIX.len())
-                                .1
-                                .trim()
-                                .to_string(),
-                        );
+                        manifest.version_semver = Some(Self::value(line, VERSION_SEMVER_PREFIX));
                     } else if line.starts_with(VERSION_STRING_PREFIX) {
                         entry = VERSION_STRING_PREFIX;
-                        manifest.version_string = Some(
-                            line.split_at(VERSION_STRING_PREFIX.len())
-                                .1
-                                .trim()
-                                .to_string(),
-                        );
+                        manifest.version_string = Some(Self::value(line, VERSION_STRING_PREFIX));
                     } else if line.starts_with(PORT_VERSION_PREFIX) {
                         entry = PORT_VERSION_PREFIX;
-                        manifest.port_version = line
-                            .split_at(PORT_VERSION_PREFIX.len())
-                            .1
-                            .trim()
+                        manifest.port_version = Self::value(line, PORT_VERSION_PREFIX)
                             .parse::<u32>()
-                            .unwrap();
+                            .unwrap_or_default();
                     } else if line.starts_with(HOMEPAGE_PREFIX) {
                         entry = HOMEPAGE_PREFIX;
-                        manifest.homepage = line.split_at(HOMEPAGE_PREFIX.len()).1.trim().to_string();
+                        manifest.homepage = Self::value(line, HOMEPAGE_PREFIX);
                     } else if line.starts_with(DESCRIPTION_PREFIX) {
                         entry = DESCRIPTION_PREFIX;
                         manifest
                             .description
-                            .push(line.split_at(DESCRIPTION_PREFIX.len()).1.trim().to_string());
+                            .push(Self::value(line, DESCRIPTION_PREFIX));
                     } else if line.starts_with(DEFAULT_FEATURES_PREFIX) {
                         entry = DEFAULT_FEATURES_PREFIX;
-                        for part in line
-                            .split_at(DEFAULT_FEATURES_PREFIX.len())
-                            .1
-                            .trim()
-                            .split(",")
-                        {
-                            manifest.default_features.push(part.trim().to_string());
-                        }
+                        manifest.default_features =
+                            Self::parse_depends(&Self::value(line, DEFAULT_FEATURES_PREFIX));
                     } else if line.starts_with(SUPPORTS_PREFIX) {
-                        manifest.supports = line.split_at(SUPPORTS_PREFIX.len()).1.trim().to_string();
-                    } else if lines.starts_with(BUILD_DEPENDS_PREFIX) {
-                        for part in lines
-                            .split_at(BUILD_DEPENDS_PREFIX.len())
-                            .1
-                            .trim()
-                            .split_whitespace()
-                        {
-                            let is_complex_feature = part.contains("[") && part.contains("]");
-                            let has_platform = part.contains("(") && part.contains(")");
-                            if !is_complex_feature && !has_platform {
-                                manifest
-                                    .dependencies
-                                    .push(VcpkgPortDependency::Simple(part.to_string()));
-                            } else {
-                                let mut dep = ComplexDependency::default();
-                                let begin = part.find("[").unwrap();
-                                let end = part.find("]").unwrap();
-                                let name = part.split_at(begin).0;
-                                dep.name = name.to_string();
-                                dep.default_features = false;
-                                for feature in part.split_at(begin).1.split_at(end).0.split(",") {
-                                    dep.features.push(feature.to_string());
-                                }
-                                manifest
-                                    .dependencies
-                                    .push(VcpkgPortDependency::Complex(dep));
-                            }
-                        }
-                    } else {
+                        entry = SUPPORTS_PREFIX;
+                        manifest.supports = Self::value(line, SUPPORTS_PREFIX);
+                    } else if line.starts_with(BUILD_DEPENDS_PREFIX) {
+                        entry = BUILD_DEPENDS_PREFIX;
+                        manifest
+                            .dependencies
+                            .extend(Self::parse_depends(&Self::value(
+                                line,
+                                BUILD_DEPENDS_PREFIX,
+                            )));
+                    } else if line.starts_with(char::is_whitespace) {
+                        // continuation line
                         if entry == DESCRIPTION_PREFIX {
                             manifest.description.push(line.trim().to_string());
+                        } else if entry == BUILD_DEPENDS_PREFIX {
+                            manifest.dependencies.extend(Self::parse_depends(line));
                         }
                     }
                 }
             }
             // feature section
-            else if lines.starts_with(FEATURES_PREFIX) {
+            else if lines.starts_with(FEATURE_PREFIX) {
+                let mut name = String::new();
+                let mut feature = VcpkgPortFeature::default();
                 for line in lines.lines() {
-                    if line.starts_with(FEATURES_PREFIX) {
+                    if line.starts_with(FEATURE_PREFIX) {
+                        name = Self::value(line, FEATURE_PREFIX);
                     } else if line.starts_with(BUILD_DEPENDS_PREFIX) {
+                        feature
+                            .dependencies
+                            .extend(Self::parse_depends(&Self::value(
+                                line,
+                                BUILD_DEPENDS_PREFIX,
+                            )));
                     } else if line.starts_with(DESCRIPTION_PREFIX) {
+                        feature
+                            .description
+                            .push(Self::value(line, DESCRIPTION_PREFIX));
                     } else if line.starts_with(SUPPORTS_PREFIX) {
+                        feature.supports = Self::value(line, SUPPORTS_PREFIX);
                     }
                 }
+                manifest.features.insert(name, feature);
             }
         }
 
+        if manifest.name.is_empty() {
+            return None;
+        }
         return Some(manifest);
     }
+
+    pub fn format_version_text(&self) -> String {
+        let v = self
+            .version
+            .as_ref()
+            .or(self.version_semver.as_ref())
+            .or(self.version_date.as_ref())
+            .or(self.version_string.as_ref())
+            .map(|v| v.clone())
+            .unwrap_or_default();
+        if self.port_version == 0 {
+            v
+        } else {
+            format!("{}#{}", v, self.port_version)
+        }
+    }
+
+    fn value(line: &str, prefix: &str) -> String {
+        line.split_at(prefix.len()).1.trim().to_string()
+    }
+
+    // zlib, libpng[core,apng] (!uwp), curl (windows)
+    fn parse_depends(text: &str) -> Vec<VcpkgPortDependency> {
+        // split by commas outside brackets and parentheses
+        let mut parts = vec![];
+        let (mut depth, mut begin) = (0, 0);
+        for (i, c) in text.char_indices() {
+            match c {
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(&text[begin..i]);
+                    begin = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(&text[begin..]);
+
+        let mut dependencies = vec![];
+        for part in parts {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+
+            let name_end = part
+                .find(|c| c == '[' || c == ' ' || c == '(')
+                .unwrap_or(part.len());
+            let name = &part[..name_end];
+            let features = match (part.find('['), part.find(']')) {
+                (Some(begin), Some(end)) if begin < end => part[begin + 1..end]
+                    .split(',')
+                    .map(|f| VcpkgPortDependency::Simple(f.trim().to_string()))
+                    .collect(),
+                _ => vec![],
+            };
+            let platform = match (part.find('('), part.rfind(')')) {
+                (Some(begin), Some(end)) if begin < end => Some(part[begin + 1..end].to_string()),
+                _ => None,
+            };
+            if features.is_empty() && platform.is_none() {
+                dependencies.push(VcpkgPortDependency::Simple(name.to_string()));
+            } else {
+                dependencies.push(VcpkgPortDependency::Complex(ComplexDependency {
+                    name: name.to_string(),
+                    features: features,
+                    platform: platform,
+                    ..Default::default()
+                }));
+            }
+        }
+        return dependencies;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_control_text() {
+        let manifest = VcpkgPortManifest::from_control_text(
+            "Source: curl\nVersion: 7.68.0\nPort-Version: 2\nBuild-Depends: zlib, openssl[core,tools] (!windows)\nDescription: A library\n\nFeature: ssl\nBuild-Depends: openssl\nDescription: SSL support\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.format_version_text(), "7.68.0#2");
+        assert_eq!(manifest.dependencies.len(), 2);
+        assert_eq!(manifest.dependencies[1].name(), "openssl");
+        assert_eq!(manifest.dependencies[1].features(), vec!["core", "tools"]);
+        assert_eq!(
+            manifest.dependencies[1].platform(),
+            Some(&String::from("!windows"))
+        );
+        assert_eq!(manifest.features["ssl"].dependencies[0].name(), "openssl");
+
+        let manifest = VcpkgPortManifest::loads(
+            r#"{"name": "fmt", "version": "11.0.2", "port-version": 1, "description": "Formatting library",
+                "dependencies": [{"name": "vcpkg-cmake", "host": true}, "zlib"]}"#,
+            false,
+        )
+        .unwrap();
+        assert_eq!(manifest.format_version_text(), "11.0.2#1");
+        assert!(manifest.dependencies[0].is_host());
+        assert_eq!(manifest.dependencies[1].name(), "zlib");
+    }
 }
@@ -9,6 +9,10 @@ use config_file_types;
 pub struct VcpkgConfiguration {
     pub default_registry: VcpkgDefaultRegistry,
     pub registries: Vec<VcpkgRegistry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overlay_ports: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overlay_triplets: Vec<String>,
 
     #[serde(skip)]
     pub path: String,
@@ -27,6 +31,9 @@ pub struct VcpkgRegistry {
     pub repository: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reference: String,
+    // filesystem registry
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub packages: Vec<String>,
 }
@@ -34,6 +41,11 @@ pub struct VcpkgRegistry {
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct VcpkgDefaultRegistry {
     pub kind: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub baseline: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub repository: String,
+    // filesystem registry
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
 }
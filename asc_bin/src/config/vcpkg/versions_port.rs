@@ -17,7 +17,12 @@ pub struct VcpkgPortVersions {
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct VcpkgPortTreeVersion {
+    // git registry
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub git_tree: String,
+    // filesystem registry, $/ was the registry root
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -26,6 +31,7 @@ pub struct VcpkgPortTreeVersion {
     pub version_semver: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_string: Option<String>,
+    #[serde(default)]
     pub port_version: u32,
 }
 
@@ -1,18 +1,28 @@
 use std::collections::BTreeSet;
 
-use crate::{cli::commands::add::AddArgs, config, errors::ErrorTag, util, vcpkg};
+use super::usage;
+use crate::{
+    cli::commands::add::AddArgs, config, errors::ErrorTag, util, vcpkg, vcpkg::version::VersionReq,
+};
 
 pub fn dependency_to_config_file(args: &AddArgs) -> bool {
+    // ports were installed into <workspace or package>/target/vcpkg_installed
+    let vcpkg_installed_dir = usage::vcpkg_installed_dir(&util::fs::get_cwd());
+
     match config::project::ProjectConfig::read_project_conf() {
         None => false,
         Some(mut project_conf) => match project_conf.workspace {
-            None => add_for_pakcage(args, &mut project_conf),
-            Some(workspace) => add_for_workspace(args, workspace),
+            None => add_for_pakcage(args, &mut project_conf, &vcpkg_installed_dir),
+            Some(workspace) => add_for_workspace(args, workspace, &vcpkg_installed_dir),
         },
     }
 }
 
-fn add_for_workspace(args: &AddArgs, workspace: config::project::WorkSpaceConfig) -> bool {
+fn add_for_workspace(
+    args: &AddArgs,
+    workspace: config::project::WorkSpaceConfig,
+    vcpkg_installed_dir: &str,
+) -> bool {
     match &args.package {
         None => {
             tracing::error!(
@@ -33,7 +43,9 @@ fn add_for_workspace(args: &AddArgs, workspace: config::project::WorkSpaceConfig
                 util::fs::set_cwd(member);
                 let result = match config::project::ProjectConfig::read_project_conf() {
                     None => false,
-                    Some(mut project_conf) => add_for_pakcage(args, &mut project_conf),
+                    Some(mut project_conf) => {
+                        add_for_pakcage(args, &mut project_conf, vcpkg_installed_dir)
+                    }
                 };
                 util::fs::set_cwd(&cwd);
                 return result;
@@ -42,48 +54,130 @@ fn add_for_workspace(args: &AddArgs, workspace: config::project::WorkSpaceConfig
     }
 }
 
-fn add_for_pakcage(args: &AddArgs, project_conf: &mut config::project::ProjectConfig) -> bool {
+fn add_for_pakcage(
+    args: &AddArgs,
+    project_conf: &mut config::project::ProjectConfig,
+    vcpkg_installed_dir: &str,
+) -> bool {
     if args.dependency.is_empty() {
         tracing::error!(
             call = "args.dependency.is_empty",
             error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
         );
         return false;
+    } else if !args.git.is_empty() && args.rev.is_empty() {
+        tracing::error!(
+            call = "args.rev.is_empty",
+            error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+            message = "--rev is required by --git"
+        );
+        return false;
+    } else if !args.path.is_empty() || !args.git.is_empty() {
+        project_conf.dependencies.insert(
+            args.dependency.clone(),
+            config::project::DependencyConfig {
+                path: args.path.replace(r"\", "/"),
+                git: args.git.clone(),
+                rev: args.rev.clone(),
+                find_packages: args
+                    .find_package
+                    .iter()
+                    .map(|s| s.clone())
+                    .collect::<BTreeSet<String>>(),
+                include_directories: args
+                    .include_directory
+                    .iter()
+                    .map(|s| s.clone())
+                    .collect::<BTreeSet<String>>(),
+                link_libraries: args
+                    .link_library
+                    .iter()
+                    .map(|s| s.clone())
+                    .collect::<BTreeSet<String>>(),
+                ..Default::default()
+            },
+        );
+        return project_conf.write_project_conf();
     } else {
+        // descending versions
+        let versions = vcpkg::search::get_port_versions(&args.registry, &args.dependency);
+        if versions.is_empty() {
+            tracing::error!(
+                call = "vcpkg::search::get_port_versions",
+                port = args.dependency,
+                error_tag = ErrorTag::VcpkgPortNotFound.as_ref(),
+                message = "try to run asc vcpkg update, asc vcpkg index"
+            );
+            return false;
+        }
+
         let mut version = args.version.clone();
         if version.is_empty() {
-            let results = vcpkg::search::from_index_file(&args.dependency, true);
-            if results.is_empty() {
-                tracing::error!(
-                    call = "vcpkg::search::from_index_file",
-                    port = args.dependency,
-                    error_tag = ErrorTag::VcpkgPortNotFound.as_ref(),
-                    message = "try to run asc vcpkg update, asc vcpkg index"
-                );
-                return false;
-            }
-            let v = results[0].split_once(']').unwrap().1.trim();
-            version = v.split_once("  ").unwrap().0.to_string();
+            version = versions[0].clone();
         } else {
-            let mut found = false;
-            let results = vcpkg::search::from_index_file(&args.dependency, true);
-            for v in &results {
-                let v = v.split_once(']').unwrap().1.trim();
-                if v.starts_with(&format!("{}  ", &version)) {
-                    found = true;
+            // exact version or requirement (^1.14, ~2.1, >=3.0,<4)
+            let selected = match VersionReq::parse(&version) {
+                None => {
+                    tracing::error!(
+                        call = "VersionReq::parse",
+                        version = version,
+                        error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                    );
+                    return false;
+                }
+                Some(requirement) => vcpkg::version::select(&requirement, &versions),
+            };
+            match selected {
+                None => {
+                    tracing::error!(
+                        call = "vcpkg::version::select",
+                        port = args.dependency,
+                        error_tag = ErrorTag::VcpkgPortVersionNotFound.as_ref(),
+                        message = format!(
+                            "try to run asc vcpkg update, asc vcpkg index\n{}",
+                            versions.join("\n")
+                        )
+                    );
+                    return false;
+                }
+                Some(v) => {
+                    tracing::info!(message = "resolve", requirement = version, version = v);
                 }
             }
-            if !found {
-                tracing::error!(
-                    call = "vcpkg::search::from_index_file",
+        }
+
+        let mut find_packages = args
+            .find_package
+            .iter()
+            .map(|s| s.clone())
+            .collect::<BTreeSet<String>>();
+        let mut link_libraries = args
+            .link_library
+            .iter()
+            .map(|s| s.clone())
+            .collect::<BTreeSet<String>>();
+        if find_packages.is_empty() && link_libraries.is_empty() {
+            let port_usage = usage::discover(
+                &args.registry,
+                &args.dependency,
+                &version,
+                vcpkg_installed_dir,
+            );
+            if !port_usage.is_complete() {
+                tracing::warn!(
+                    message = "can't discover find_package and link libraries, set them with --find-package and --link-library",
                     port = args.dependency,
-                    error_tag = ErrorTag::VcpkgPortVersionNotFound.as_ref(),
-                    message = format!(
-                        "try to run asc vcpkg update, asc vcpkg index\n{}",
-                        results.join("\n")
-                    )
                 );
-                return false;
+            }
+            if !port_usage.is_empty() {
+                tracing::info!(
+                    message = "discover",
+                    port = args.dependency,
+                    find_packages = format!("{:?}", port_usage.find_packages),
+                    link_libraries = format!("{:?}", port_usage.link_libraries),
+                );
+                find_packages = port_usage.find_packages;
+                link_libraries = port_usage.link_libraries;
             }
         }
 
@@ -91,26 +185,20 @@ fn add_for_pakcage(args: &AddArgs, project_conf: &mut config::project::ProjectCo
             args.dependency.clone(),
             config::project::DependencyConfig {
                 version: version,
-                find_packages: args
-                    .find_package
-                    .iter()
-                    .map(|s| s.clone())
-                    .collect::<BTreeSet<String>>(),
+                registry: args.registry.clone(),
+                find_packages: find_packages,
                 include_directories: args
                     .include_directory
                     .iter()
                     .map(|s| s.clone())
                     .collect::<BTreeSet<String>>(),
-                link_libraries: args
-                    .link_library
-                    .iter()
-                    .map(|s| s.clone())
-                    .collect::<BTreeSet<String>>(),
+                link_libraries: link_libraries,
                 features: args
                     .feature
                     .iter()
                     .map(|s| s.clone())
                     .collect::<BTreeSet<String>>(),
+                ..Default::default()
             },
         );
         return project_conf.write_project_conf();
@@ -0,0 +1,123 @@
+use crate::{
+    cli::commands::InfoArgs,
+    config::vcpkg::port_manifest::VcpkgPortDependency,
+    errors::ErrorTag,
+    vcpkg::{self, version::VersionReq},
+};
+
+pub fn print(args: &InfoArgs) -> bool {
+    if VersionReq::parse(&args.version).is_none() {
+        tracing::error!(
+            call = "VersionReq::parse",
+            version = args.version,
+            error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+        );
+        return false;
+    }
+
+    let (registry, version, commit) = match vcpkg::search::get_port_version_commit_info(
+        &args.registry,
+        &args.port,
+        &args.version,
+    ) {
+        None => {
+            tracing::error!(
+                call = "vcpkg::search::get_port_version_commit_info",
+                port = args.port,
+                version = args.version,
+                error_tag = ErrorTag::VcpkgPortNotFound.as_ref(),
+                message = "try to run asc vcpkg update, asc vcpkg index"
+            );
+            return false;
+        }
+        Some(info) => info,
+    };
+
+    let manifest = match vcpkg::search::get_port_manifest(&registry, &args.port, &version) {
+        None => {
+            tracing::error!(
+                call = "vcpkg::search::get_port_manifest",
+                port = args.port,
+                version = version,
+                error_tag = ErrorTag::VcpkgPortNotFound.as_ref(),
+            );
+            return false;
+        }
+        Some(manifest) => manifest,
+    };
+
+    println!("{}  {version}  [{registry}]", manifest.name);
+    // filesystem registry has no commits
+    if !commit.hash.is_empty() {
+        println!("    commit: {}  {}", commit.hash, commit.date_time);
+    }
+    if !manifest.description.is_empty() {
+        println!("    description: {}", manifest.description.join(" "));
+    }
+    if !manifest.homepage.is_empty() {
+        println!("    homepage: {}", manifest.homepage);
+    }
+    if let Some(license) = &manifest.license {
+        println!("    license: {license}");
+    }
+    if !manifest.supports.is_empty() {
+        println!("    supports: {}", manifest.supports);
+    }
+
+    if !manifest.default_features.is_empty() {
+        println!(
+            "    default features: {}",
+            manifest
+                .default_features
+                .iter()
+                .map(|f| format_dependency(f))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+    if !manifest.features.is_empty() {
+        println!("    features:");
+        for (name, feature) in &manifest.features {
+            println!("        {name}: {}", feature.description.join(" "));
+            if !feature.supports.is_empty() {
+                println!("            supports: {}", feature.supports);
+            }
+            for dep in &feature.dependencies {
+                println!("            {}", format_dependency(dep));
+            }
+        }
+    }
+
+    if !manifest.dependencies.is_empty() {
+        println!("    dependencies:");
+        for dep in &manifest.dependencies {
+            println!("        {}", format_dependency(dep));
+        }
+    }
+
+    let versions = vcpkg::search::get_port_versions(&registry, &args.port);
+    if !versions.is_empty() {
+        println!("    versions: {}", versions.join(", "));
+    }
+
+    return true;
+}
+
+// fmt[core,x] >= 10.0 (windows) host
+fn format_dependency(dep: &VcpkgPortDependency) -> String {
+    let mut text = dep.name().to_string();
+    let features = dep.features();
+    if !features.is_empty() {
+        text += &format!("[{}]", features.join(","));
+    }
+    if let Some(version) = dep.version_ge() {
+        text += &format!(" >= {version}");
+    }
+    if let Some(platform) = dep.platform() {
+        text += &format!(" ({platform})");
+    }
+    if dep.is_host() {
+        text += " host";
+    }
+    return text;
+}
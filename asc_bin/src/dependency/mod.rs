@@ -1,4 +1,11 @@
 pub mod add;
 pub mod copy;
 pub mod find;
+pub mod info;
 pub mod remove;
+pub mod source;
+pub mod suggest;
+pub mod tree;
+pub mod unused;
+pub mod update;
+pub mod usage;
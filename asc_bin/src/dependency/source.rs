@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cli::commands::ScanArgs,
+    config::{
+        project::{DependencyConfig, ProjectConfig},
+        relative_paths, system_paths,
+    },
+    errors::ErrorTag,
+    git, util,
+};
+
+// git or path dependency which was built from source with add_subdirectory
+#[derive(Debug, Default, Clone)]
+pub struct SourceDependency {
+    pub name: String,
+    // dir contains CMakeLists.txt
+    pub cmake_dir: String,
+    // lib target -> include dir, only asc projects export them automatically
+    pub libs: BTreeMap<String, String>,
+    // vcpkg dependencies of asc projects
+    pub dependencies: BTreeMap<String, DependencyConfig>,
+}
+
+// asc projects were generated into output_dir, their source trees were left untouched
+pub fn resolve(
+    name: &str,
+    dep: &DependencyConfig,
+    package_dir: &str,
+    output_dir: &str,
+    cmake_minimum_version: &str,
+) -> Option<SourceDependency> {
+    let source_dir = if dep.is_git() {
+        fetch_git(name, dep)?
+    } else if std::path::Path::new(&dep.path).is_absolute() {
+        util::fs::normalize_path(&dep.path)
+    } else {
+        util::fs::normalize_path(&format!("{package_dir}/{}", dep.path))
+    };
+
+    let mut result = SourceDependency {
+        name: name.to_string(),
+        cmake_dir: source_dir.clone(),
+        ..Default::default()
+    };
+
+    let asc_toml_path = format!("{source_dir}/{}", relative_paths::ASC_TOML_FILE_NAME);
+    if !util::fs::is_file_exists(&asc_toml_path) {
+        // cmake project, targets were linked with link_libraries
+        let cmake_lists_path =
+            format!("{source_dir}/{}", relative_paths::CMAKE_LISTS_TXT_FILE_NAME);
+        if !util::fs::is_file_exists(&cmake_lists_path) {
+            tracing::error!(
+                func = "util::fs::is_file_exists",
+                error_tag = ErrorTag::FileNotFoundError.as_ref(),
+                dependency = name,
+                path = cmake_lists_path,
+                message = "neither asc nor cmake project"
+            );
+            return None;
+        }
+        return Some(result);
+    }
+
+    // asc project, generate cmake without configuring
+    let cwd = util::fs::get_cwd();
+    util::fs::set_cwd(&source_dir);
+    let scanned = ScanArgs {
+        cmake_minimum_version: cmake_minimum_version.to_string(),
+        suggest_deps: false,
+        skip_configure: true,
+        output_dir: output_dir.to_string(),
+    }
+    .exec();
+    util::fs::set_cwd(&cwd);
+    if !scanned {
+        return None;
+    }
+    result.cmake_dir = format!("{output_dir}/{}", relative_paths::ASC_PROJECT_DIR_NAME);
+
+    let project_conf = ProjectConfig::load(&asc_toml_path, false)?;
+    let packages = match &project_conf.workspace {
+        None => vec![(source_dir.clone(), project_conf)],
+        Some(workspace) => workspace
+            .load_sorted_members(&source_dir)?
+            .into_iter()
+            .map(|(member, conf, _)| (format!("{source_dir}/{member}"), conf))
+            .collect(),
+    };
+    for (package_dir, conf) in packages {
+        for lib in &conf.libs {
+            result.libs.insert(
+                lib.name.clone(),
+                format!("{package_dir}/{}", lib.source_dir),
+            );
+        }
+        for (n, d) in conf.dependencies {
+            if !d.is_source() {
+                result.dependencies.insert(n, d);
+            }
+        }
+    }
+
+    return Some(result);
+}
+
+fn fetch_git(name: &str, dep: &DependencyConfig) -> Option<String> {
+    if dep.rev.is_empty() {
+        tracing::error!(
+            error_tag = ErrorTag::InvalidProjectError.as_ref(),
+            dependency = name,
+            git = dep.git,
+            message = "rev is required by git dependency"
+        );
+        return None;
+    }
+
+    // cached by url and rev, branches and tags were re-fetched since they may be moved
+    let clone_dir = system_paths::DataPath::git_dependency_clone_dir(name, &dep.git, &dep.rev);
+    if util::fs::is_dir_exists(&clone_dir) {
+        if is_commit_id(&dep.rev) {
+            return Some(clone_dir);
+        }
+        if git::fetch::run(&clone_dir) && checkout_rev(&clone_dir, &dep.rev) {
+            return Some(clone_dir);
+        }
+        tracing::error!(
+            func = "git::fetch::run",
+            error_tag = ErrorTag::ExecuteCommandError.as_ref(),
+            dependency = name,
+            git = dep.git,
+            rev = dep.rev,
+        );
+        return None;
+    }
+
+    if !git::clone::run(&dep.git, "", &clone_dir, &vec![]) || !checkout_rev(&clone_dir, &dep.rev) {
+        tracing::error!(
+            func = "git::clone::run",
+            error_tag = ErrorTag::ExecuteCommandError.as_ref(),
+            dependency = name,
+            git = dep.git,
+            rev = dep.rev,
+        );
+        if util::fs::is_dir_exists(&clone_dir) {
+            util::fs::remove_dirs(&clone_dir);
+        }
+        return None;
+    }
+
+    return Some(clone_dir);
+}
+
+// remote branch before tag, local branch of a clone was not updated by fetch
+fn checkout_rev(clone_dir: &str, rev: &str) -> bool {
+    if is_commit_id(rev) {
+        return git::checkout::run(clone_dir, rev);
+    }
+    return git::checkout::run(clone_dir, &format!("origin/{rev}"))
+        || git::checkout::run(clone_dir, rev);
+}
+
+// full or abbreviated commit hash, otherwise branch or tag
+fn is_commit_id(rev: &str) -> bool {
+    return (7..=64).contains(&rev.len()) && rev.chars().all(|c| c.is_ascii_hexdigit());
+}
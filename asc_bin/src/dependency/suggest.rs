@@ -0,0 +1,449 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    cli::commands::{AddArgs, VcpkgArgs},
+    config::{
+        project::{ProjectConfig, ScannedFiles},
+        relative_paths,
+        system_paths::DataPath,
+    },
+    util, vcpkg,
+};
+
+use super::usage;
+
+static INCLUDE_DIR: &str = "include/";
+static PORT_FILE_INCLUDE_DIR: &str = "${CURRENT_PACKAGES_DIR}/include/";
+static PORT_FILE_REMOVE: &str = "REMOVE";
+static GIT_HEAD: &str = "HEAD";
+
+// headers of c standard library and common system apis
+static SYSTEM_HEADERS: [&str; 40] = [
+    "assert.h",
+    "complex.h",
+    "ctype.h",
+    "errno.h",
+    "fenv.h",
+    "float.h",
+    "inttypes.h",
+    "iso646.h",
+    "limits.h",
+    "locale.h",
+    "math.h",
+    "setjmp.h",
+    "signal.h",
+    "stdalign.h",
+    "stdarg.h",
+    "stdatomic.h",
+    "stdbool.h",
+    "stddef.h",
+    "stdint.h",
+    "stdio.h",
+    "stdlib.h",
+    "stdnoreturn.h",
+    "string.h",
+    "tgmath.h",
+    "threads.h",
+    "time.h",
+    "uchar.h",
+    "wchar.h",
+    "wctype.h",
+    "dirent.h",
+    "dlfcn.h",
+    "fcntl.h",
+    "poll.h",
+    "pthread.h",
+    "unistd.h",
+    "windows.h",
+    "winsock2.h",
+    "ws2tcpip.h",
+    "io.h",
+    "direct.h",
+];
+static SYSTEM_HEADER_DIRS: [&str; 5] = ["sys/", "arpa/", "netinet/", "linux/", "mach/"];
+
+// missing port -> includes
+type MissingPorts = BTreeMap<String, BTreeSet<String>>;
+
+pub fn missing_dependencies(project_dir: &str) -> bool {
+    let project_conf = match ProjectConfig::read_project_conf() {
+        None => return false,
+        Some(conf) => conf,
+    };
+
+    let packages = match packages_of_project(&project_conf, project_dir) {
+        None => return false,
+        Some(packages) => packages,
+    };
+
+    let vcpkg_installed_dir = usage::vcpkg_installed_dir(project_dir);
+    let installed_headers = index_installed_headers(&vcpkg_installed_dir);
+
+    // port files of registries were read only if some includes were not installed
+    let mut registry_headers = None;
+
+    let mut suggestions = BTreeMap::<Option<String>, MissingPorts>::new();
+    for (member, conf) in &packages {
+        let mut missing = MissingPorts::new();
+        for include in third_party_includes(project_dir, member, conf, true).keys() {
+            let port = match installed_headers.get(include) {
+                Some(port) => Some(port.clone()),
+                None => {
+                    let (headers, ports) =
+                        registry_headers.get_or_insert_with(index_registry_headers);
+                    guess_port(include, headers, ports)
+                }
+            };
+            if let Some(port) = port {
+                if !conf.dependencies.contains_key(&port) {
+                    missing
+                        .entry(port)
+                        .or_insert_with(BTreeSet::new)
+                        .insert(include.clone());
+                }
+            }
+        }
+        if !missing.is_empty() {
+            suggestions.insert(member.clone(), missing);
+        }
+    }
+
+    if suggestions.is_empty() {
+        tracing::warn!("no missing dependencies were found");
+        return true;
+    }
+
+    for (member, missing) in &suggestions {
+        if let Some(m) = member {
+            println!("{m}:");
+        }
+        for (port, includes) in missing {
+            println!(
+                "    {port}  ({})",
+                includes.iter().cloned().collect::<Vec<String>>().join(", ")
+            );
+        }
+    }
+    println!("Do you want to add missing dependencies, yes or no? ");
+    let mut choose = String::new();
+    std::io::stdin().read_line(&mut choose).unwrap();
+    if ![String::from("y"), String::from("yes")].contains(&choose.trim().to_lowercase()) {
+        return true;
+    }
+
+    let mut result = true;
+    for (member, missing) in suggestions {
+        for port in missing.keys() {
+            util::fs::set_cwd(project_dir);
+            result &= AddArgs {
+                dependency: port.clone(),
+                package: member.clone(),
+                ..Default::default()
+            }
+            .exec();
+        }
+    }
+    util::fs::set_cwd(project_dir);
+
+    return result;
+}
+
+// fmt/core.h -> fmt
+pub fn index_installed_headers(vcpkg_installed_dir: &str) -> BTreeMap<String, String> {
+    let mut headers = BTreeMap::new();
+    for (port, files) in usage::installed_files(vcpkg_installed_dir) {
+        for file in files {
+            if let Some(header) = file.strip_prefix(INCLUDE_DIR) {
+                if !header.is_empty() && !header.ends_with('/') {
+                    headers.insert(header.to_string(), port.clone());
+                }
+            }
+        }
+    }
+    return headers;
+}
+
+// (workspace member, asc.toml)
+pub fn packages_of_project(
+    project_conf: &ProjectConfig,
+    project_dir: &str,
+) -> Option<Vec<(Option<String>, ProjectConfig)>> {
+    let mut packages = vec![];
+    match &project_conf.workspace {
+        None => packages.push((None, project_conf.clone())),
+        Some(workspace) => {
+            // workspace = true dependencies were inherited
+            for (member, member_conf, _) in workspace.load_sorted_members(project_dir)? {
+                packages.push((Some(member), member_conf));
+            }
+        }
+    }
+    return Some(packages);
+}
+
+// third-party include -> sources of all targets in package, system headers were excluded
+// angled_only keeps #include <...> only, #include "..." of ports were used too
+pub fn third_party_includes(
+    project_dir: &str,
+    member: &Option<String>,
+    conf: &ProjectConfig,
+    angled_only: bool,
+) -> BTreeMap<String, BTreeSet<String>> {
+    let package_dir = match member {
+        None => project_dir.to_string(),
+        Some(m) => format!("{project_dir}/{m}"),
+    };
+
+    let mut names = vec![];
+    names.extend(conf.bins.iter().map(|e| e.name.clone()));
+    names.extend(conf.libs.iter().map(|e| e.name.clone()));
+    names.extend(
+        conf.get_examples(&package_dir)
+            .iter()
+            .map(|e| e.name.clone()),
+    );
+    names.extend(conf.get_tests(&package_dir).iter().map(|e| e.name.clone()));
+
+    let mut includes = BTreeMap::<String, BTreeSet<String>>::new();
+    for name in names {
+        if let Some(scanned) = ScannedFiles::load(
+            &format!(
+                "{project_dir}/{}/{name}/{}",
+                relative_paths::ASC_PROJECT_DIR_NAME,
+                relative_paths::SCANNED_FILES_TOML_FILE_NAME
+            ),
+            true,
+        ) {
+            let scanned_includes = if angled_only {
+                scanned.third_party_angled_includes
+            } else {
+                scanned.third_party_includes
+            };
+            for (include, sources) in scanned_includes {
+                if is_system_header(&include) {
+                    continue;
+                }
+                includes
+                    .entry(include)
+                    .or_insert_with(BTreeSet::new)
+                    .extend(sources);
+            }
+        }
+    }
+    return includes;
+}
+
+fn is_system_header(include: &str) -> bool {
+    // c++ standard library headers have no extension
+    if !include.contains('.') && !include.contains('/') {
+        return true;
+    }
+    return SYSTEM_HEADERS.contains(&include)
+        || SYSTEM_HEADER_DIRS
+            .iter()
+            .any(|dir| include.starts_with(dir));
+}
+
+// include dir -> port, from DESTINATION ${CURRENT_PACKAGES_DIR}/include/<dir> of port files
+// and port names (fmt/core.h, sqlite3.h), ports of registries declared first were kept
+fn index_registry_headers() -> (BTreeMap<String, String>, BTreeSet<String>) {
+    let mut headers = BTreeMap::new();
+    let mut ports = BTreeSet::new();
+
+    let vcpkg_args = VcpkgArgs::load_or_default();
+    for (registry, _url, _branch, vcpkg_root_dir) in vcpkg_args.flatten_registry() {
+        let baseline = match vcpkg::search::get_baseline(&vcpkg_args, &registry, GIT_HEAD) {
+            None => continue,
+            Some(baseline) => baseline,
+        };
+        for (port_name, version) in &baseline.default {
+            if !ports.insert(port_name.clone()) {
+                continue;
+            }
+            let port_file_path =
+                DataPath::vcpkg_ports_port_file_cmake_path(&vcpkg_root_dir, port_name);
+            let text = if util::fs::is_file_exists(&port_file_path) {
+                std::fs::read_to_string(&port_file_path).ok()
+            } else {
+                vcpkg::search::get_port_file(
+                    &registry,
+                    port_name,
+                    &version.format_version_text(),
+                    relative_paths::VCPKG_PORT_FILE_CMAKE_FILE_NAME,
+                )
+            };
+            if let Some(text) = text {
+                index_port_file(port_name, &text, &mut headers);
+            }
+        }
+    }
+    return (headers, ports);
+}
+
+fn index_port_file(port_name: &str, text: &str, headers: &mut BTreeMap<String, String>) {
+    // file(REMOVE_RECURSE ...) of installed dirs were not destinations
+    for line in text.lines().filter(|line| !line.contains(PORT_FILE_REMOVE)) {
+        let mut rest = line;
+        while let Some(pos) = rest.find(PORT_FILE_INCLUDE_DIR) {
+            rest = &rest[pos + PORT_FILE_INCLUDE_DIR.len()..];
+            let dir = rest
+                .split(|c: char| c == '"' || c == ')' || c == '$' || c.is_whitespace())
+                .next()
+                .unwrap_or_default()
+                .trim_matches('/');
+            if !dir.is_empty() {
+                headers
+                    .entry(dir.to_string())
+                    .or_insert_with(|| port_name.to_string());
+            }
+        }
+    }
+}
+
+// boost/asio/io_context.hpp -> boost/asio, boost in index
+// boost/asio.hpp -> boost-asio, boost, curl/curl.h -> curl, sqlite3.h -> sqlite3 in ports
+fn guess_port(
+    include: &str,
+    headers: &BTreeMap<String, String>,
+    ports: &BTreeSet<String>,
+) -> Option<String> {
+    let components = include.split('/').collect::<Vec<&str>>();
+    for i in (1..components.len()).rev() {
+        if let Some(port) = headers.get(&components[..i].join("/")) {
+            return Some(port.clone());
+        }
+    }
+
+    let mut candidates = vec![];
+    if components.len() > 1 {
+        let second = components[1].split('.').next().unwrap_or_default();
+        candidates.push(format!("{}-{second}", components[0]));
+        candidates.push(components[0].to_string());
+    } else {
+        candidates.push(include.split('.').next().unwrap_or_default().to_string());
+    }
+
+    for candidate in candidates {
+        let name = candidate.replace('_', "-").to_lowercase();
+        if !name.is_empty() && ports.contains(&name) {
+            return Some(name);
+        }
+    }
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::project::EntryConfig;
+
+    #[test]
+    fn test_is_system_header() {
+        assert!(is_system_header("iostream"));
+        assert!(is_system_header("stdio.h"));
+        assert!(is_system_header("sys/types.h"));
+        assert!(!is_system_header("fmt/core.h"));
+        assert!(!is_system_header("sqlite3.h"));
+    }
+
+    #[test]
+    fn test_third_party_includes() {
+        let project_dir = std::env::temp_dir()
+            .join(format!("asc_suggest_{}", std::process::id()))
+            .to_string_lossy()
+            .replace(r"\", "/");
+        let scanned_dir = format!("{project_dir}/{}/a", relative_paths::ASC_PROJECT_DIR_NAME);
+        std::fs::create_dir_all(&scanned_dir).unwrap();
+        let scanned = ScannedFiles {
+            third_party_includes: BTreeMap::from([
+                (
+                    String::from("fmt/core.h"),
+                    BTreeSet::from([String::from("main.cpp")]),
+                ),
+                (
+                    String::from("spdlog/spdlog.h"),
+                    BTreeSet::from([String::from("log.cpp")]),
+                ),
+                (
+                    String::from("stdio.h"),
+                    BTreeSet::from([String::from("main.cpp")]),
+                ),
+            ]),
+            third_party_angled_includes: BTreeMap::from([(
+                String::from("fmt/core.h"),
+                BTreeSet::from([String::from("main.cpp")]),
+            )]),
+            path: format!(
+                "{scanned_dir}/{}",
+                relative_paths::SCANNED_FILES_TOML_FILE_NAME
+            ),
+            ..Default::default()
+        };
+        assert!(scanned.dump(false, false));
+
+        let mut conf = ProjectConfig::default();
+        conf.bins.insert(EntryConfig {
+            name: String::from("a"),
+            ..Default::default()
+        });
+
+        // #include "spdlog/spdlog.h" was used but not suggested
+        let includes = third_party_includes(&project_dir, &None, &conf, false);
+        assert_eq!(
+            includes.keys().collect::<Vec<&String>>(),
+            vec!["fmt/core.h", "spdlog/spdlog.h"]
+        );
+        let angled = third_party_includes(&project_dir, &None, &conf, true);
+        assert_eq!(angled.keys().collect::<Vec<&String>>(), vec!["fmt/core.h"]);
+
+        std::fs::remove_dir_all(&project_dir).unwrap();
+    }
+
+    #[test]
+    fn test_guess_port() {
+        let mut headers = BTreeMap::new();
+        index_port_file(
+            "asio",
+            r#"file(INSTALL "${SOURCE_PATH}/asio/include/asio" DESTINATION "${CURRENT_PACKAGES_DIR}/include")
+file(INSTALL "${SOURCE_PATH}/asio/include/asio.hpp" DESTINATION "${CURRENT_PACKAGES_DIR}/include")"#,
+            &mut headers,
+        );
+        index_port_file(
+            "nlohmann-json",
+            r#"vcpkg_cmake_config_fixup(PACKAGE_NAME nlohmann_json)
+file(REMOVE_RECURSE ${CURRENT_PACKAGES_DIR}/include/nlohmann/detail/old)
+file(COPY "${SOURCE_PATH}/single_include/" DESTINATION "${CURRENT_PACKAGES_DIR}/include/nlohmann")"#,
+            &mut headers,
+        );
+        index_port_file(
+            "json-other",
+            r#"file(INSTALL ${SOURCE_PATH}/json.hpp DESTINATION ${CURRENT_PACKAGES_DIR}/include/nlohmann)"#,
+            &mut headers,
+        );
+        assert_eq!(headers.get("nlohmann").unwrap(), "nlohmann-json");
+        assert!(!headers.contains_key("nlohmann/detail/old"));
+        assert!(!headers.contains_key(""));
+
+        let ports = ["asio", "boost-asio", "curl", "sqlite3", "nlohmann-json"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<BTreeSet<String>>();
+        assert_eq!(
+            guess_port("nlohmann/json.hpp", &headers, &ports),
+            Some(String::from("nlohmann-json"))
+        );
+        assert_eq!(
+            guess_port("boost/asio.hpp", &headers, &ports),
+            Some(String::from("boost-asio"))
+        );
+        assert_eq!(
+            guess_port("curl/curl.h", &headers, &ports),
+            Some(String::from("curl"))
+        );
+        assert_eq!(
+            guess_port("sqlite3.h", &headers, &ports),
+            Some(String::from("sqlite3"))
+        );
+        assert_eq!(guess_port("unknown/x.h", &headers, &ports), None);
+    }
+}
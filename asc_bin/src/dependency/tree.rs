@@ -0,0 +1,469 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::{
+    cli::commands::{TreeArgs, VcpkgArgs},
+    config::{
+        project::{DependencyConfig, LockConfig, ProjectConfig},
+        relative_paths,
+        vcpkg::{port_manifest::VcpkgPortManifest, versions_baseline::VcpkgBaseline},
+    },
+    errors::ErrorTag,
+    util,
+    vcpkg::{
+        self,
+        version::{PortVersion, VersionReq},
+    },
+};
+
+static VCPKG_FEATURE_PLATFORM_DELIMITER: &str = "@";
+static VCPKG_CORE_FEATURE_NAME: &str = "core";
+static GIT_HEAD: &str = "HEAD";
+
+#[derive(Debug, Default, Clone)]
+struct Edge {
+    features: BTreeSet<String>,
+    platform: Option<String>,
+    host: bool,
+    // (!) markers, version conflicts
+    conflicts: Vec<String>,
+    // path or git dependency
+    source: Option<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Node {
+    version: String,
+    features: BTreeSet<String>,
+    dependencies: BTreeMap<String, Edge>,
+}
+
+#[derive(Debug, Default)]
+struct PortGraph {
+    lock: LockConfig,
+    baselines: BTreeMap<String, Option<VcpkgBaseline>>,
+    manifests: BTreeMap<String, Option<VcpkgPortManifest>>,
+    roots: BTreeMap<String, BTreeMap<String, Edge>>,
+    nodes: BTreeMap<String, Node>,
+    // ports whose requirements of workspace members can't be merged
+    conflicts: BTreeSet<String>,
+}
+
+pub fn print(args: &TreeArgs) -> bool {
+    let cwd = util::fs::get_cwd();
+    let project_conf = match ProjectConfig::read_project_conf() {
+        None => return false,
+        Some(c) => c,
+    };
+
+    // package or workspace members with their direct dependencies
+    let mut packages = BTreeMap::new();
+    let mut dependencies = BTreeMap::<String, DependencyConfig>::new();
+    let mut conflicts = BTreeSet::new();
+    match &project_conf.workspace {
+        None => {
+            packages.insert(
+                project_conf.package.as_ref().unwrap().name.clone(),
+                project_conf.dependencies.clone(),
+            );
+            dependencies = project_conf.dependencies.clone();
+        }
+        Some(workspace) => {
+            let sorted_members = match workspace.load_sorted_members(&cwd) {
+                None => return false,
+                Some(sorted_members) => sorted_members,
+            };
+            // conflicts were marked on edges instead of stopping, first requirement was resolved
+            for (member, member_conf, _) in sorted_members {
+                for (name, dep) in &member_conf.dependencies {
+                    match dependencies.get_mut(name) {
+                        None => {
+                            dependencies.insert(name.clone(), dep.clone());
+                        }
+                        Some(merged) => {
+                            let mut candidate = merged.clone();
+                            if candidate.merge(dep) {
+                                *merged = candidate;
+                            } else {
+                                tracing::warn!(
+                                    "{member} requires {name} {}, which conflicts with {}",
+                                    dep.version,
+                                    merged.version
+                                );
+                                conflicts.insert(name.clone());
+                            }
+                        }
+                    }
+                }
+                packages.insert(member, member_conf.dependencies);
+            }
+        }
+    }
+
+    let lock_path = format!("{cwd}/{}", relative_paths::ASC_LOCK_FILE_NAME);
+    let mut lock = LockConfig::load(&lock_path, true).unwrap();
+    if !lock.is_up_to_date(&dependencies) {
+        tracing::warn!(
+            "{} is out of date, resolve versions without writing it",
+            relative_paths::ASC_LOCK_FILE_NAME
+        );
+        lock = match vcpkg::json::resolve_lock(&dependencies, &lock_path) {
+            None => return false,
+            Some(lock) => lock,
+        };
+    }
+
+    let mut graph = PortGraph {
+        lock: lock,
+        conflicts: conflicts,
+        ..Default::default()
+    };
+    graph.build(&packages);
+
+    match &args.invert {
+        None => {
+            for (root, edges) in &graph.roots {
+                println!("{root}");
+                let mut printed = BTreeSet::new();
+                graph.print_children(edges, args, "", 1, &mut printed);
+                println!();
+            }
+        }
+        Some(port) => {
+            if !graph.nodes.contains_key(port) {
+                tracing::error!(
+                    func = "graph.nodes.contains_key",
+                    error_tag = ErrorTag::VcpkgPortNotFound.as_ref(),
+                    port = port,
+                );
+                return false;
+            }
+            println!("{}", graph.format_node(port, None, args));
+            let mut printed = BTreeSet::new();
+            graph.print_dependents(port, args, "", 1, &mut printed);
+        }
+    }
+
+    return true;
+}
+
+impl PortGraph {
+    fn build(&mut self, packages: &BTreeMap<String, BTreeMap<String, DependencyConfig>>) {
+        // requirements of same port from workspace members, shown on conflicted edges
+        let mut requirements = BTreeMap::<String, BTreeSet<String>>::new();
+        for deps in packages.values() {
+            for (name, dep) in deps {
+                if !dep.is_source() {
+                    requirements
+                        .entry(name.clone())
+                        .or_default()
+                        .insert(dep.version.clone());
+                }
+            }
+        }
+
+        let mut queue = VecDeque::new();
+        for (package, deps) in packages {
+            let mut edges = BTreeMap::new();
+            for (name, dep) in deps {
+                let mut edge = Edge::default();
+                if dep.is_source() {
+                    edge.source = Some(if dep.is_git() {
+                        format!("git {} @ {}", dep.git, dep.rev)
+                    } else {
+                        format!("path {}", dep.path)
+                    });
+                    edges.insert(name.clone(), edge);
+                    continue;
+                }
+
+                edge.features = dep
+                    .features
+                    .iter()
+                    .map(|f| match f.split_once(VCPKG_FEATURE_PLATFORM_DELIMITER) {
+                        None => f.clone(),
+                        Some((n, _p)) => n.to_string(),
+                    })
+                    .collect();
+                if self.conflicts.contains(name) {
+                    edge.conflicts.push(format!(
+                        "requirements ({})",
+                        requirements[name]
+                            .iter()
+                            .map(|r| r.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(" | ")
+                    ));
+                }
+                if let Some(locked) = self.lock.packages.get(name) {
+                    if let Some(req) = VersionReq::parse(&dep.version) {
+                        if !req.matches(&PortVersion::parse(&locked.format_version_text())) {
+                            edge.conflicts.push(format!("requires {}", dep.version));
+                        }
+                    }
+                }
+                // default features were disabled in vcpkg.json if any feature was specified
+                queue.push_back((name.clone(), edge.features.clone(), dep.features.is_empty()));
+                edges.insert(name.clone(), edge);
+            }
+            self.roots.insert(package.clone(), edges);
+        }
+
+        while let Some((port, features, default_features)) = queue.pop_front() {
+            let manifest = self.get_manifest(&port);
+            let is_new = !self.nodes.contains_key(&port);
+            let node = self.nodes.entry(port.clone()).or_default();
+
+            // expand dependencies of newly requested features only
+            let mut requested = features.clone();
+            if default_features {
+                if let Some(m) = &manifest {
+                    requested.extend(m.default_features.iter().map(|f| f.name().to_string()));
+                }
+            }
+            requested.remove(VCPKG_CORE_FEATURE_NAME);
+            let new_features = requested
+                .difference(&node.features)
+                .map(|f| f.clone())
+                .collect::<Vec<String>>();
+            if !is_new && new_features.is_empty() {
+                continue;
+            }
+            node.features.extend(new_features.clone());
+
+            let manifest = match manifest {
+                None => continue,
+                Some(m) => m,
+            };
+            node.version = manifest.format_version_text();
+
+            let mut deps = vec![];
+            if is_new {
+                deps.extend(manifest.dependencies.iter());
+            }
+            for f in &new_features {
+                if let Some(feature) = manifest.features.get(f) {
+                    deps.extend(feature.dependencies.iter());
+                }
+            }
+
+            for dep in deps {
+                let dep_features = dep.features().into_iter().collect::<BTreeSet<String>>();
+                let edge = node.dependencies.entry(dep.name().to_string()).or_default();
+                edge.features.extend(dep_features.clone());
+                edge.platform = dep.platform().map(|p| p.clone());
+                edge.host = dep.is_host();
+                if let Some(min) = dep.version_ge() {
+                    let constraint = format!(">={min}");
+                    if !edge.conflicts.contains(&constraint) {
+                        edge.conflicts.push(constraint);
+                    }
+                }
+                queue.push_back((dep.name().to_string(), dep_features, dep.default_features()));
+            }
+        }
+
+        // keep version>= constraints which were not satisfied only
+        let versions = self
+            .nodes
+            .iter()
+            .map(|(name, node)| (name.clone(), node.version.clone()))
+            .collect::<BTreeMap<String, String>>();
+        for node in self.nodes.values_mut() {
+            for (name, edge) in node.dependencies.iter_mut() {
+                let version = PortVersion::parse(versions.get(name).unwrap_or(&String::new()));
+                edge.conflicts.retain(|c| match c.strip_prefix(">=") {
+                    None => true,
+                    Some(min) => VersionReq::parse(&format!(">={min}"))
+                        .map(|r| !r.matches(&version))
+                        .unwrap_or(false),
+                });
+                for c in edge.conflicts.iter_mut() {
+                    if c.starts_with(">=") {
+                        *c = format!("requires {c}");
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_manifest(&mut self, port: &str) -> Option<VcpkgPortManifest> {
+        if let Some(manifest) = self.manifests.get(port) {
+            return manifest.clone();
+        }
+
+        let manifest = self.resolve_version(port).and_then(|(registry, version)| {
+            vcpkg::search::get_port_manifest(&registry, port, &version)
+        });
+        if manifest.is_none() {
+            tracing::warn!("can't found manifest of {port}");
+        }
+        self.manifests.insert(port.to_string(), manifest.clone());
+        return manifest;
+    }
+
+    // locked version of direct dependencies, registry baseline of transitive dependencies
+    fn resolve_version(&mut self, port: &str) -> Option<(String, String)> {
+        if let Some(locked) = self.lock.packages.get(port) {
+            return Some((locked.registry.clone(), locked.format_version_text()));
+        }
+
+        let mut registries = vec![relative_paths::VCPKG_DIR_NAME.to_string()];
+        registries.extend(self.lock.registries.keys().map(|r| r.clone()));
+        for registry in registries {
+            if let Some(baseline) = self.get_baseline(&registry) {
+                if let Some(version) = baseline.default.get(port) {
+                    return Some((registry, version.format_version_text()));
+                }
+            }
+        }
+        return None;
+    }
+
+    fn get_baseline(&mut self, registry: &str) -> Option<&VcpkgBaseline> {
+        if !self.baselines.contains_key(registry) {
+            let hash = self
+                .lock
+                .registries
+                .get(registry)
+                .map(|r| r.baseline.clone())
+                .unwrap_or(GIT_HEAD.to_string());
+            let baseline =
+                vcpkg::search::get_baseline(&VcpkgArgs::load_or_default(), registry, &hash);
+            self.baselines.insert(registry.to_string(), baseline);
+        }
+        return self.baselines.get(registry).unwrap().as_ref();
+    }
+
+    fn format_node(&self, name: &str, edge: Option<&Edge>, args: &TreeArgs) -> String {
+        let mut text = name.to_string();
+        if let Some(node) = self.nodes.get(name) {
+            if !node.version.is_empty() {
+                text.push_str(&format!(" v{}", node.version));
+            }
+        }
+        if let Some(edge) = edge {
+            if let Some(source) = &edge.source {
+                text.push_str(&format!(" ({source})"));
+            }
+            if args.features && !edge.features.is_empty() {
+                text.push_str(&format!(
+                    " [{}]",
+                    edge.features
+                        .iter()
+                        .map(|f| f.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                ));
+            }
+            if edge.host {
+                text.push_str(" (host)");
+            }
+            if args.features {
+                if let Some(platform) = &edge.platform {
+                    text.push_str(&format!(" ({platform})"));
+                }
+            }
+            for conflict in &edge.conflicts {
+                text.push_str(&format!(" (!) {conflict}"));
+            }
+        }
+        return text;
+    }
+
+    fn print_children(
+        &self,
+        edges: &BTreeMap<String, Edge>,
+        args: &TreeArgs,
+        prefix: &str,
+        depth: usize,
+        printed: &mut BTreeSet<String>,
+    ) {
+        if args.depth.is_some_and(|d| depth > d) {
+            return;
+        }
+
+        for (index, (name, edge)) in edges.iter().enumerate() {
+            let is_last = index + 1 == edges.len();
+            let (branch, indent) = if is_last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            let mut line = format!(
+                "{prefix}{branch}{}",
+                self.format_node(name, Some(edge), args)
+            );
+            let node = self.nodes.get(name);
+            let expand = node.is_some_and(|n| !n.dependencies.is_empty());
+            // duplicates were expanded once
+            if expand && !printed.insert(name.clone()) {
+                line.push_str(" (*)");
+                println!("{line}");
+                continue;
+            }
+            println!("{line}");
+
+            if expand {
+                self.print_children(
+                    &node.unwrap().dependencies,
+                    args,
+                    &format!("{prefix}{indent}"),
+                    depth + 1,
+                    printed,
+                );
+            }
+        }
+    }
+
+    fn print_dependents(
+        &self,
+        port: &str,
+        args: &TreeArgs,
+        prefix: &str,
+        depth: usize,
+        printed: &mut BTreeSet<String>,
+    ) {
+        if args.depth.is_some_and(|d| depth > d) {
+            return;
+        }
+
+        let mut dependents = vec![];
+        for (name, node) in &self.nodes {
+            if let Some(edge) = node.dependencies.get(port) {
+                dependents.push((name.clone(), edge, false));
+            }
+        }
+        for (root, edges) in &self.roots {
+            if let Some(edge) = edges.get(port) {
+                dependents.push((root.clone(), edge, true));
+            }
+        }
+
+        for (index, (name, edge, is_root)) in dependents.iter().enumerate() {
+            let is_last = index + 1 == dependents.len();
+            let (branch, indent) = if is_last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            let mut line = format!(
+                "{prefix}{branch}{}",
+                self.format_node(name, Some(edge), args)
+            );
+            if *is_root {
+                println!("{line}");
+                continue;
+            }
+            if !printed.insert(name.clone()) {
+                line.push_str(" (*)");
+                println!("{line}");
+                continue;
+            }
+            println!("{line}");
+
+            self.print_dependents(name, args, &format!("{prefix}{indent}"), depth + 1, printed);
+        }
+    }
+}
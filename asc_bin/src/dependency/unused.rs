@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    cli::commands::DepsArgs,
+    config::{project::ProjectConfig, relative_paths},
+    errors::ErrorTag,
+    util,
+};
+
+use super::{suggest, usage};
+
+static INCLUDE_DIR: &str = "include/";
+static LIB_DIRS: [&str; 2] = ["lib/", "debug/lib/"];
+// makefile and ninja generators
+static CMAKE_LINK_FILE_NAMES: [&str; 2] = ["link.txt", "build.ninja"];
+
+pub fn check(args: &DepsArgs) -> bool {
+    if !args.unused {
+        tracing::error!(
+            call = "!args.unused",
+            error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+        );
+        return false;
+    }
+
+    if !ProjectConfig::is_source_scaned() {
+        return false;
+    }
+
+    let project_dir = util::fs::get_cwd();
+    let project_conf = match ProjectConfig::read_project_conf() {
+        None => return false,
+        Some(conf) => conf,
+    };
+
+    let vcpkg_installed_dir = usage::vcpkg_installed_dir(&project_dir);
+    let installed_files = usage::installed_files(&vcpkg_installed_dir);
+    if installed_files.is_empty() {
+        tracing::error!(
+            error_tag = ErrorTag::DirectoryNotFoundError.as_ref(),
+            path = vcpkg_installed_dir,
+            message = "build first to install vcpkg ports"
+        );
+        return false;
+    }
+    let link_commands = read_link_commands(&format!(
+        "{project_dir}/{}",
+        relative_paths::ASC_TARGET_DIR_NAME
+    ));
+
+    let mut result = true;
+    let packages = match suggest::packages_of_project(&project_conf, &project_dir) {
+        None => return false,
+        Some(packages) => packages,
+    };
+    for (member, conf) in packages {
+        let includes = suggest::third_party_includes(&project_dir, &member, &conf, false);
+
+        let mut unused = vec![];
+        for (name, dep) in &conf.dependencies {
+            // git and path dependencies were built from sources
+            if dep.is_source() {
+                continue;
+            }
+            match installed_files.get(name) {
+                None => {
+                    tracing::warn!(message = "port was not installed", name = name);
+                }
+                Some(files) => {
+                    if !is_used(files, &includes, &link_commands) {
+                        unused.push(name.clone());
+                    }
+                }
+            }
+        }
+
+        if unused.is_empty() {
+            continue;
+        }
+        if let Some(m) = &member {
+            println!("{m}:");
+        }
+        for name in &unused {
+            println!("    {name}");
+        }
+
+        if args.remove {
+            println!("Do you want to remove them from asc.toml, yes or no? ");
+            let mut choose = String::new();
+            std::io::stdin().read_line(&mut choose).unwrap();
+            if ![String::from("y"), String::from("yes")].contains(&choose.trim().to_lowercase()) {
+                continue;
+            }
+
+            // reload to keep workspace = true dependencies of member as they were
+            let mut raw_conf = match &member {
+                None => project_conf.clone(),
+                Some(m) => match ProjectConfig::load(
+                    &format!("{project_dir}/{m}/{}", relative_paths::ASC_TOML_FILE_NAME),
+                    false,
+                ) {
+                    None => return false,
+                    Some(c) => c,
+                },
+            };
+            for name in &unused {
+                raw_conf.dependencies.remove(name);
+            }
+            result &= raw_conf.dump(true, false);
+        }
+    }
+
+    return result;
+}
+
+// header only ports were used if any header was included
+// ports without headers (tools, meta ports) were used if any library was linked
+fn is_used(
+    files: &BTreeSet<String>,
+    includes: &BTreeMap<String, BTreeSet<String>>,
+    link_commands: &str,
+) -> bool {
+    let headers = files
+        .iter()
+        .filter_map(|f| f.strip_prefix(INCLUDE_DIR))
+        .collect::<Vec<&str>>();
+    if !headers.is_empty() {
+        return headers.iter().any(|h| includes.contains_key(*h));
+    }
+
+    return files
+        .iter()
+        .filter(|f| LIB_DIRS.iter().any(|dir| f.starts_with(dir)))
+        .filter_map(|f| f.rsplit('/').next())
+        .any(|lib| !lib.is_empty() && link_commands.contains(lib));
+}
+
+fn read_link_commands(target_dir: &str) -> String {
+    let mut text = String::new();
+    for entry in walkdir::WalkDir::new(target_dir)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != relative_paths::VCPKG_INSTALLED_DIR_NAME)
+        .filter_map(|e| e.ok())
+    {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if CMAKE_LINK_FILE_NAMES.contains(&file_name.as_str()) {
+            if let Ok(t) = std::fs::read_to_string(entry.path()) {
+                text.push_str(&t);
+            }
+        }
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(items: &[&str]) -> BTreeSet<String> {
+        items.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn test_is_used() {
+        let mut includes = BTreeMap::new();
+        includes.insert(String::from("fmt/core.h"), set(&["/work/a/src/main.cpp"]));
+        let link_commands = "/usr/bin/c++ main.cpp.o -o a ../vcpkg_installed/x64-linux/lib/libz.a";
+
+        // header only port
+        let nlohmann_json = set(&["include/nlohmann/", "include/nlohmann/json.hpp"]);
+        assert!(!is_used(&nlohmann_json, &includes, link_commands));
+        let fmt = set(&["include/fmt/", "include/fmt/core.h", "lib/libfmt.a"]);
+        assert!(is_used(&fmt, &includes, link_commands));
+
+        // #include "spdlog/spdlog.h" of scanned third_party_includes
+        includes.insert(
+            String::from("spdlog/spdlog.h"),
+            set(&["/work/a/src/log.cpp"]),
+        );
+        let spdlog = set(&["include/spdlog/", "include/spdlog/spdlog.h"]);
+        assert!(is_used(&spdlog, &includes, link_commands));
+
+        // library only port
+        let zlib = set(&["lib/libz.a", "debug/lib/libz.a", "share/zlib/copyright"]);
+        assert!(is_used(&zlib, &includes, link_commands));
+        let bzip2 = set(&["lib/libbz2.a", "debug/lib/libbz2d.a"]);
+        assert!(!is_used(&bzip2, &includes, link_commands));
+
+        // neither headers nor libraries
+        let tool = set(&["tools/protoc", "share/protoc/copyright"]);
+        assert!(!is_used(&tool, &includes, link_commands));
+    }
+}
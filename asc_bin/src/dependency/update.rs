@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cli::commands::UpdateArgs,
+    config::{self, relative_paths},
+    errors::ErrorTag,
+    util,
+    vcpkg::{
+        self,
+        version::{self, PortVersion, VersionReq},
+    },
+};
+
+pub fn dependencies_in_config_file(args: &UpdateArgs) -> bool {
+    let cwd = util::fs::get_cwd();
+
+    match config::project::ProjectConfig::read_project_conf() {
+        None => false,
+        Some(mut project_conf) => {
+            let mut dependencies = BTreeMap::new();
+            let mut result = true;
+            match &project_conf.workspace {
+                None => {
+                    result &= update_for_package(args, &mut project_conf, true);
+                    dependencies.extend(project_conf.dependencies.clone());
+                }
+                Some(workspace) => {
+                    let mut workspace = workspace.clone();
+                    if let Some(member) = &args.package {
+                        if !workspace.members.contains(member) {
+                            tracing::error!(
+                                error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                                packages = workspace.get_members()
+                            );
+                            return false;
+                        }
+                    }
+
+                    // [workspace.dependencies] were shared by all members
+                    if args.package.is_none() {
+                        let (r, changed) = update_dependencies(args, &mut workspace.dependencies);
+                        result &= r;
+                        if changed {
+                            project_conf.workspace = Some(workspace.clone());
+                            result &= project_conf.write_project_conf();
+                        }
+                    }
+
+                    for member in &workspace.members {
+                        util::fs::set_cwd(member);
+                        if let Some(mut member_conf) =
+                            config::project::ProjectConfig::read_project_conf()
+                        {
+                            if args.package.is_none() || args.package.as_ref() == Some(member) {
+                                result &= update_for_package(
+                                    args,
+                                    &mut member_conf,
+                                    args.package.is_some(),
+                                );
+                            }
+                            result &= workspace.inherit_dependencies(member, &mut member_conf)
+                                && config::project::WorkSpaceConfig::merge_dependencies(
+                                    &mut dependencies,
+                                    member,
+                                    &member_conf.dependencies,
+                                );
+                        }
+                        util::fs::set_cwd(&cwd);
+                    }
+                }
+            }
+
+            // re-resolve baselines even if nothing was upgraded
+            let lock_path = format!("{cwd}/{}", relative_paths::ASC_LOCK_FILE_NAME);
+            if !args.dry_run && util::fs::is_file_exists(&lock_path) {
+                tracing::warn!("update {}", relative_paths::ASC_LOCK_FILE_NAME);
+                result &= vcpkg::json::gen_lock(&dependencies, &lock_path);
+            }
+
+            return result;
+        }
+    }
+}
+
+// strict: every dependency name in args must be in the package
+fn update_for_package(
+    args: &UpdateArgs,
+    project_conf: &mut config::project::ProjectConfig,
+    strict: bool,
+) -> bool {
+    for name in &args.dependencies {
+        if strict && !project_conf.dependencies.contains_key(name) {
+            tracing::error!(
+                call = "project_conf.dependencies.contains_key",
+                dependency = name,
+                error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+            );
+            return false;
+        }
+    }
+
+    let (mut result, changed) = update_dependencies(args, &mut project_conf.dependencies);
+    if changed {
+        result &= project_conf.write_project_conf();
+    }
+
+    return result;
+}
+
+// returns (no error, any version was changed)
+fn update_dependencies(
+    args: &UpdateArgs,
+    dependencies: &mut BTreeMap<String, config::project::DependencyConfig>,
+) -> (bool, bool) {
+    let mut result = true;
+    let mut changed = false;
+    for (name, dep) in dependencies.iter_mut() {
+        if dep.is_source() || (!args.dependencies.is_empty() && !args.dependencies.contains(name)) {
+            continue;
+        }
+        if dep.is_workspace() {
+            tracing::info!(
+                dependency = name,
+                message = "inherited from workspace.dependencies"
+            );
+            continue;
+        }
+
+        // descending versions
+        let versions = vcpkg::search::get_port_versions(&dep.registry, name);
+        if versions.is_empty() {
+            tracing::error!(
+                call = "vcpkg::search::get_port_versions",
+                port = name,
+                error_tag = ErrorTag::VcpkgPortNotFound.as_ref(),
+                message = "try to run asc vcpkg update, asc vcpkg index"
+            );
+            result = false;
+            continue;
+        }
+
+        let requirement = match VersionReq::parse(&dep.version) {
+            None => {
+                tracing::error!(
+                    call = "VersionReq::parse",
+                    dependency = name,
+                    version = dep.version,
+                    error_tag = ErrorTag::InvalidProjectError.as_ref(),
+                );
+                result = false;
+                continue;
+            }
+            Some(r) => r,
+        };
+
+        let latest = &versions[0];
+        let current = version::select(&requirement, &versions)
+            .map(|v| v.clone())
+            .unwrap_or_default();
+        if &current == latest {
+            tracing::info!(
+                dependency = name,
+                version = dep.version,
+                message = "up to date"
+            );
+            continue;
+        }
+
+        // exact version upgrades in same major version, requirement was resolved by asc.lock
+        let compatible = if requirement.is_exact() {
+            VersionReq::parse(&format!("^{}", PortVersion::parse(&current).text))
+                .and_then(|r| version::select(&r, &versions).map(|v| v.clone()))
+                .unwrap_or(current.clone())
+        } else {
+            current.clone()
+        };
+        tracing::warn!(
+            dependency = name,
+            requirement = dep.version,
+            current = current,
+            compatible = compatible,
+            latest = latest,
+        );
+
+        let upgrade = if args.breaking && !requirement.matches(&PortVersion::parse(latest)) {
+            requirement.format_upgrade_text(&PortVersion::parse(latest))
+        } else if requirement.is_exact() && compatible != current {
+            compatible
+        } else {
+            continue;
+        };
+        if !args.dry_run {
+            dep.version = upgrade;
+            changed = true;
+        }
+    }
+
+    return (result, changed);
+}
@@ -0,0 +1,352 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    config::relative_paths,
+    util,
+    vcpkg::{self, search::get_port_version_commit_info},
+};
+
+static VCPKG_SHARE_DIR_NAME: &str = "share";
+static VCPKG_INFO_DIR_NAME: &str = "vcpkg/info";
+static VCPKG_LIST_FILE_EXT: &str = ".list";
+static VCPKG_USAGE_FILE_NAME: &str = "usage";
+static CMAKE_FIND_PACKAGE: &str = "find_package(";
+static CMAKE_TARGET_LINK_LIBRARIES: &str = "target_link_libraries(";
+static CMAKE_ADD_LIBRARY: &str = "add_library(";
+static CMAKE_IMPORTED: &str = "IMPORTED";
+static CMAKE_CONFIG_SUFFIXES: [&str; 2] = ["Config.cmake", "-config.cmake"];
+static CMAKE_TARGETS_SUFFIXES: [&str; 2] = ["Targets.cmake", "-targets.cmake"];
+static CMAKE_LINK_SCOPES: [&str; 3] = ["PRIVATE", "PUBLIC", "INTERFACE"];
+
+// ports whose usage was generated at install time or was ambiguous
+// (port, find_packages, link_libraries)
+static KNOWN_USAGES: [(&str, &[&str], &[&str]); 14] = [
+    ("boost-headers", &["Boost"], &["Boost::headers"]),
+    ("curl", &["CURL"], &["CURL::libcurl"]),
+    ("fmt", &["fmt"], &["fmt::fmt"]),
+    ("gtest", &["GTest"], &["GTest::gtest", "GTest::gtest_main"]),
+    (
+        "libuv",
+        &["libuv"],
+        &["$<IF:$<TARGET_EXISTS:libuv::uv_a>,libuv::uv_a,libuv::uv>"],
+    ),
+    ("libpng", &["PNG"], &["PNG::PNG"]),
+    (
+        "nlohmann-json",
+        &["nlohmann_json"],
+        &["nlohmann_json::nlohmann_json"],
+    ),
+    (
+        "openssl",
+        &["OpenSSL"],
+        &["OpenSSL::SSL", "OpenSSL::Crypto"],
+    ),
+    ("protobuf", &["protobuf"], &["protobuf::libprotobuf"]),
+    ("spdlog", &["spdlog"], &["spdlog::spdlog"]),
+    (
+        "sqlite3",
+        &["unofficial-sqlite3"],
+        &["unofficial::sqlite3::sqlite3"],
+    ),
+    ("yaml-cpp", &["yaml-cpp"], &["yaml-cpp::yaml-cpp"]),
+    ("zlib", &["ZLIB"], &["ZLIB::ZLIB"]),
+    (
+        "zstd",
+        &["zstd"],
+        &["$<IF:$<TARGET_EXISTS:zstd::libzstd_shared>,zstd::libzstd_shared,zstd::libzstd_static>"],
+    ),
+];
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PortUsage {
+    pub find_packages: BTreeSet<String>,
+    pub link_libraries: BTreeSet<String>,
+}
+
+impl PortUsage {
+    pub fn is_empty(&self) -> bool {
+        self.find_packages.is_empty() && self.link_libraries.is_empty()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.find_packages.is_empty() && !self.link_libraries.is_empty()
+    }
+
+    pub fn extend(&mut self, other: PortUsage) {
+        self.find_packages.extend(other.find_packages);
+        self.link_libraries.extend(other.link_libraries);
+    }
+}
+
+// curated table, installed usage and cmake exports, registry usage
+// partial results of sources were merged until both find_packages and link_libraries were found
+pub fn discover(
+    registry: &str,
+    port_name: &str,
+    version: &str,
+    vcpkg_installed_dir: &str,
+) -> PortUsage {
+    if let Some((_port, find_packages, link_libraries)) =
+        KNOWN_USAGES.iter().find(|(port, _, _)| *port == port_name)
+    {
+        return PortUsage {
+            find_packages: find_packages.iter().map(|s| s.to_string()).collect(),
+            link_libraries: link_libraries.iter().map(|s| s.to_string()).collect(),
+        };
+    }
+
+    let mut usage = from_installed(port_name, vcpkg_installed_dir);
+    if usage.is_complete() {
+        return usage;
+    }
+
+    if let Some((registry, version, _commit)) =
+        get_port_version_commit_info(registry, port_name, version)
+    {
+        if let Some(text) =
+            vcpkg::search::get_port_file(&registry, port_name, &version, VCPKG_USAGE_FILE_NAME)
+        {
+            usage.extend(parse_usage(&text));
+        }
+    }
+
+    return usage;
+}
+
+// <vcpkg_installed>/<triplet>/share/<port>/usage or *Config.cmake and *Targets.cmake
+fn from_installed(port_name: &str, vcpkg_installed_dir: &str) -> PortUsage {
+    let mut usage = PortUsage::default();
+
+    let triplets = match std::fs::read_dir(vcpkg_installed_dir) {
+        Err(_) => return usage,
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.path().to_string_lossy().replace(r"\", "/"))
+            .collect::<Vec<String>>(),
+    };
+
+    for triplet_dir in triplets {
+        let share_dir = format!("{triplet_dir}/{VCPKG_SHARE_DIR_NAME}/{port_name}");
+        if !util::fs::is_dir_exists(&share_dir) {
+            continue;
+        }
+
+        let usage_path = format!("{share_dir}/{VCPKG_USAGE_FILE_NAME}");
+        if let Ok(text) = std::fs::read_to_string(&usage_path) {
+            usage.extend(parse_usage(&text));
+            if usage.is_complete() {
+                return usage;
+            }
+        }
+
+        for entry in walkdir::WalkDir::new(&share_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            for suffix in CMAKE_CONFIG_SUFFIXES {
+                if let Some(package) = file_name.strip_suffix(suffix) {
+                    if !package.is_empty() {
+                        usage.find_packages.insert(package.to_string());
+                    }
+                }
+            }
+            if CMAKE_TARGETS_SUFFIXES
+                .iter()
+                .any(|suffix| file_name.ends_with(suffix))
+            {
+                if let Ok(text) = std::fs::read_to_string(entry.path()) {
+                    usage.link_libraries.extend(parse_imported_targets(&text));
+                }
+            }
+        }
+        if usage.is_complete() {
+            return usage;
+        }
+    }
+
+    return usage;
+}
+
+// find_package(fmt CONFIG REQUIRED)
+// target_link_libraries(main PRIVATE fmt::fmt)
+pub fn parse_usage(text: &str) -> PortUsage {
+    let mut usage = PortUsage::default();
+
+    let lines = text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    for args in find_calls(&lines, CMAKE_FIND_PACKAGE) {
+        if let Some(package) = args.first() {
+            usage.find_packages.insert(package.clone());
+        }
+    }
+
+    for args in find_calls(&lines, CMAKE_TARGET_LINK_LIBRARIES) {
+        // skip target name and scopes
+        for arg in args.iter().skip(1) {
+            if !CMAKE_LINK_SCOPES.contains(&arg.as_str()) {
+                usage.link_libraries.insert(arg.clone());
+            }
+        }
+    }
+
+    return usage;
+}
+
+// add_library(fmt::fmt STATIC IMPORTED)
+fn parse_imported_targets(text: &str) -> Vec<String> {
+    find_calls(text, CMAKE_ADD_LIBRARY)
+        .into_iter()
+        .filter(|args| args.iter().any(|a| a == CMAKE_IMPORTED))
+        .filter_map(|args| args.first().map(|a| a.clone()))
+        .collect()
+}
+
+// whitespace separated arguments of cmake command calls, genex were kept as one argument
+fn find_calls(text: &str, command: &str) -> Vec<Vec<String>> {
+    let mut results = vec![];
+    let mut rest = text;
+    while let Some(begin) = rest.find(command) {
+        rest = &rest[begin + command.len()..];
+
+        let mut depth = 0;
+        let mut end = rest.len();
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => {
+                    end = i;
+                    break;
+                }
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        results.push(
+            rest[..end]
+                .split_whitespace()
+                .map(|a| a.to_string())
+                .collect(),
+        );
+        rest = &rest[end..];
+    }
+    return results;
+}
+
+pub fn vcpkg_installed_dir(project_dir: &str) -> String {
+    format!(
+        "{project_dir}/{}/{}",
+        relative_paths::ASC_TARGET_DIR_NAME,
+        relative_paths::VCPKG_INSTALLED_DIR_NAME
+    )
+}
+
+// <vcpkg_installed>/vcpkg/info/<port>_<version>_<triplet>.list
+// port -> installed files without triplet prefix (include/fmt/core.h, lib/libfmt.a)
+pub fn installed_files(vcpkg_installed_dir: &str) -> BTreeMap<String, BTreeSet<String>> {
+    let mut files = BTreeMap::<String, BTreeSet<String>>::new();
+
+    let info_dir = format!("{vcpkg_installed_dir}/{VCPKG_INFO_DIR_NAME}");
+    let entries = match std::fs::read_dir(&info_dir) {
+        Err(_) => return files,
+        Ok(entries) => entries,
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let port = match file_name
+            .strip_suffix(VCPKG_LIST_FILE_EXT)
+            .and_then(|stem| stem.split_once('_'))
+        {
+            None => continue,
+            Some((port, _version_triplet)) => port.to_string(),
+        };
+        if let Ok(text) = std::fs::read_to_string(entry.path()) {
+            let port_files = files.entry(port).or_insert_with(BTreeSet::new);
+            for line in text.lines() {
+                if let Some((_triplet, file)) = line.split_once('/') {
+                    port_files.insert(file.to_string());
+                }
+            }
+        }
+    }
+
+    return files;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_usage() {
+        let usage = parse_usage(
+            r#"The package openssl is compatible with built-in CMake targets:
+
+    find_package(OpenSSL REQUIRED)
+    target_link_libraries(main PRIVATE OpenSSL::SSL)
+    # target_link_libraries(main PRIVATE commented::out)
+    target_link_libraries(main PRIVATE
+        $<IF:$<TARGET_EXISTS:OpenSSL::Crypto>,OpenSSL::Crypto,OpenSSL::Crypto>)
+"#,
+        );
+        assert_eq!(
+            usage.find_packages,
+            BTreeSet::from([String::from("OpenSSL")])
+        );
+        assert_eq!(
+            usage.link_libraries,
+            BTreeSet::from([
+                String::from(
+                    "$<IF:$<TARGET_EXISTS:OpenSSL::Crypto>,OpenSSL::Crypto,OpenSSL::Crypto>"
+                ),
+                String::from("OpenSSL::SSL"),
+            ])
+        );
+
+        assert_eq!(
+            parse_imported_targets("add_library(fmt::fmt STATIC IMPORTED)\nadd_library(x STATIC)"),
+            vec![String::from("fmt::fmt")]
+        );
+    }
+
+    #[test]
+    fn test_from_installed() {
+        let dir = std::env::temp_dir()
+            .join(format!("asc_usage_{}", std::process::id()))
+            .to_string_lossy()
+            .replace(r"\", "/");
+        let share_dir = format!("{dir}/x64-linux/{VCPKG_SHARE_DIR_NAME}/cjson");
+        std::fs::create_dir_all(&share_dir).unwrap();
+        std::fs::write(
+            format!("{share_dir}/{VCPKG_USAGE_FILE_NAME}"),
+            "find_package(cJSON CONFIG REQUIRED)\n",
+        )
+        .unwrap();
+
+        // usage file without target_link_libraries was not complete
+        let usage = from_installed("cjson", &dir);
+        assert!(!usage.is_empty());
+        assert!(!usage.is_complete());
+
+        // link libraries of cmake exports were merged into find_packages of usage file
+        std::fs::write(
+            format!("{share_dir}/cjsonTargets.cmake"),
+            "add_library(cjson SHARED IMPORTED)\n",
+        )
+        .unwrap();
+        let usage = from_installed("cjson", &dir);
+        assert!(usage.is_complete());
+        assert_eq!(usage.find_packages, BTreeSet::from([String::from("cJSON")]));
+        assert_eq!(
+            usage.link_libraries,
+            BTreeSet::from([String::from("cjson")])
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
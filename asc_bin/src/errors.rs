@@ -50,4 +50,9 @@ pub enum ErrorTag {
     // vcpkg
     VcpkgPortNotFound,
     VcpkgPortVersionNotFound,
+    VcpkgPortAmbiguous,
+    // process
+    ExecuteCommandError,
+    // test
+    TestFailedError,
 }
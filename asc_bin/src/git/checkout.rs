@@ -0,0 +1,26 @@
+use crate::util;
+
+pub fn run(repo_root_dir: &str, rev: &str) -> bool {
+    util::shell::run(
+        "git",
+        &vec!["checkout", "--detach", rev],
+        repo_root_dir,
+        false,
+        false,
+        false,
+    )
+    .is_ok_and(|output| output.status.success())
+}
+
+// switch to local branch which was reset to the remote branch
+pub fn branch(repo_root_dir: &str, branch: &str) -> bool {
+    util::shell::run(
+        "git",
+        &vec!["checkout", "-B", branch, &format!("origin/{branch}")],
+        repo_root_dir,
+        false,
+        false,
+        false,
+    )
+    .is_ok_and(|output| output.status.success())
+}
@@ -1,7 +1,12 @@
 use crate::util;
 
 pub fn run(url: &str, branch: &str, directory: &str, arguments: &Vec<String>) -> bool {
-    let mut args = vec!["clone", "-b", branch, url, directory];
+    let mut args = vec!["clone"];
+    // default branch of remote
+    if !branch.is_empty() {
+        args.extend(["-b", branch]);
+    }
+    args.extend([url, directory]);
     for a in arguments {
         args.push(a);
     }
@@ -8,6 +8,8 @@ pub static GIT_LOG_FORMAT_ABBR_COMMIT_HASH_DATE: &str =
     r#"--pretty=format:{"hash": "%h", "date_time": "%ad"}"#;
 pub static GIT_LOG_FORMAT_VERSION_STAT: &str =
     r#"--pretty=format:commit %H%nDate:   %ad%n%n    %s%n"#;
+static GIT_HEAD: &str = "HEAD";
+static GIT_TREE_MODE: &str = "040000";
 
 // from vcpkg (git log)
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -156,3 +158,143 @@ pub fn get_changed_commits(
 
     return commits;
 }
+
+// trees of <sub_path>/<name> added or modified by each commit after since_commit, oldest first
+// one git log instead of ls-tree per commit, merge commits have no diffs
+pub fn get_changed_trees(
+    repo_root_dir: &str,
+    since_commit: &str,
+    sub_path: &str,
+) -> Vec<(GitCommitInfo, Vec<(String, String)>)> {
+    let mut commits = vec![];
+
+    let range = if since_commit.is_empty() {
+        String::from(GIT_HEAD)
+    } else {
+        format!("{since_commit}..{GIT_HEAD}")
+    };
+    let output = util::shell::run(
+        "git",
+        &vec![
+            "log",
+            "--reverse",
+            "--date=iso",
+            "-r",
+            "-t",
+            "--raw",
+            "--no-abbrev",
+            "--no-renames",
+            GIT_LOG_FORMAT_COMMIT_HASH_DATE,
+            &range,
+            "--",
+            sub_path,
+        ],
+        repo_root_dir,
+        true,
+        false,
+        true,
+    )
+    .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    for line in stdout.lines() {
+        if line.starts_with("{") {
+            match serde_json::from_str(line) {
+                Err(e) => {
+                    tracing::error!(
+                        call = "serde_json::from_str",
+                        line = line,
+                        error_tag = ErrorTag::JsonDeserializeError.as_ref(),
+                        message = e.to_string()
+                    );
+                }
+                Ok(info) => {
+                    commits.push((info, vec![]));
+                }
+            }
+        } else if let Some(tree) = parse_raw_tree_line(line, sub_path) {
+            if let Some((_commit, trees)) = commits.last_mut() {
+                trees.push(tree);
+            }
+        }
+    }
+
+    return commits;
+}
+
+pub fn get_merge_commits(repo_root_dir: &str, since_commit: &str) -> Vec<GitCommitInfo> {
+    let mut commits = vec![];
+
+    let range = if since_commit.is_empty() {
+        String::from(GIT_HEAD)
+    } else {
+        format!("{since_commit}..{GIT_HEAD}")
+    };
+    let output = util::shell::run(
+        "git",
+        &vec![
+            "log",
+            "--merges",
+            "--reverse",
+            "--date=iso",
+            GIT_LOG_FORMAT_COMMIT_HASH_DATE,
+            &range,
+        ],
+        repo_root_dir,
+        true,
+        false,
+        true,
+    )
+    .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    for line in stdout.lines() {
+        if let Ok(info) = serde_json::from_str(line) {
+            commits.push(info);
+        }
+    }
+
+    return commits;
+}
+
+// :040000 040000 <old tree> <new tree> M\tports/fmt -> (new tree, fmt)
+fn parse_raw_tree_line(line: &str, sub_path: &str) -> Option<(String, String)> {
+    let (meta, path) = line.strip_prefix(':')?.split_once('\t')?;
+    let fields = meta.split_whitespace().collect::<Vec<&str>>();
+    if fields.len() < 5 || fields[1] != GIT_TREE_MODE {
+        return None;
+    }
+    let name = path.trim().strip_prefix(sub_path)?;
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+    return Some((fields[3].to_string(), name.to_string()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_raw_tree_line() {
+        assert_eq!(
+            parse_raw_tree_line(":040000 040000 1a2b3c 4d5e6f M\tports/fmt", "ports/"),
+            Some((String::from("4d5e6f"), String::from("fmt")))
+        );
+        assert_eq!(
+            parse_raw_tree_line(":000000 040000 0000000 4d5e6f A\tports/fmt", "ports/"),
+            Some((String::from("4d5e6f"), String::from("fmt")))
+        );
+        assert_eq!(
+            parse_raw_tree_line(
+                ":100644 100644 1a2b3c 4d5e6f M\tports/fmt/vcpkg.json",
+                "ports/"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_raw_tree_line(":040000 000000 1a2b3c 0000000 D\tports/fmt", "ports/"),
+            None
+        );
+    }
+}
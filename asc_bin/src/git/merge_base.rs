@@ -0,0 +1,13 @@
+use crate::util;
+
+pub fn is_ancestor(repo_root_dir: &str, ancestor: &str, rev: &str) -> bool {
+    util::shell::run(
+        "git",
+        &vec!["merge-base", "--is-ancestor", ancestor, rev],
+        repo_root_dir,
+        true,
+        true,
+        true,
+    )
+    .is_ok_and(|output| output.status.success())
+}
@@ -1,11 +1,13 @@
 pub mod add;
 pub mod archive;
+pub mod checkout;
 pub mod clone;
 pub mod commit;
 pub mod commit_amend;
 pub mod fetch;
 pub mod log;
 pub mod ls_tree;
+pub mod merge_base;
 pub mod push;
 pub mod reset;
 pub mod rev_parse;
@@ -16,3 +16,20 @@ pub fn run(rev: &str, port_name: &str, repo_root_dir: &str) -> String {
 
     return String::from_utf8_lossy(&output.stdout).trim().to_string();
 }
+
+// current branch name, HEAD if detached, None if HEAD was not resolved
+pub fn abbrev_ref(repo_root_dir: &str) -> Option<String> {
+    let output = util::shell::run(
+        "git",
+        &vec!["rev-parse", "--abbrev-ref", "HEAD"],
+        repo_root_dir,
+        true,
+        true,
+        true,
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -19,3 +19,20 @@ pub fn run(repo_root_dir: &str, hash: &str) -> String {
     .unwrap();
     String::from_utf8_lossy(&output.stdout).to_string()
 }
+
+// file content at tree-ish (commit or git tree hash), None if not exists
+pub fn file(repo_root_dir: &str, tree_ish: &str, path: &str) -> Option<String> {
+    let output = util::shell::run(
+        "git",
+        &vec!["show", &format!("{tree_ish}:{path}")],
+        repo_root_dir,
+        true,
+        true,
+        true,
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
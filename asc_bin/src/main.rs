@@ -49,6 +49,10 @@ fn main() {
         cli::Commands::Search(options) => {
             options.exec();
         }
+        // show port details
+        cli::Commands::Info(options) => {
+            options.exec();
+        }
         // add lib
         cli::Commands::Add(options) => {
             options.exec();
@@ -57,6 +61,22 @@ fn main() {
         cli::Commands::Remove(options) => {
             options.exec();
         }
+        // upgrade libs
+        cli::Commands::Update(options) => {
+            options.exec();
+        }
+        // print dependency tree
+        cli::Commands::Tree(options) => {
+            options.exec();
+        }
+        // check unused libs
+        cli::Commands::Deps(options) => {
+            options.exec();
+        }
+        // manage binary cache
+        cli::Commands::Cache(options) => {
+            options.exec();
+        }
 
         // scan source tree
         cli::Commands::Scan(options) => {
@@ -71,6 +91,14 @@ fn main() {
         cli::Commands::Run(options) => {
             options.exec();
         }
+        // build and run tests
+        cli::Commands::Test(options) => {
+            options.exec();
+        }
+        // build and run tests with coverage
+        cli::Commands::Coverage(options) => {
+            options.exec();
+        }
         // clean target
         cli::Commands::Clean(options) => {
             options.exec();
@@ -111,3 +111,62 @@ pub fn remove_dirs(path: &str) -> bool {
         }
     }
 }
+
+// copy files of source dir recursively, existing files in target dir were overwritten
+pub fn copy_dir(source_dir: &str, target_dir: &str) -> bool {
+    for entry in walkdir::WalkDir::new(source_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let source = entry.path().to_string_lossy().replace(r"\", "/");
+        let target = format!(
+            "{target_dir}/{}",
+            super::remove_prefix(&source, &source_dir.to_string(), &target_dir.to_string())
+        );
+        if !create_dirs(&get_parent_dir(&target)) {
+            return false;
+        }
+        if let Err(e) = std::fs::copy(&source, &target) {
+            tracing::error!(
+                func = "std::fs::copy",
+                source = source,
+                target = target,
+                error_tag = ErrorTag::WriteFileError.as_ref(),
+                error_str = e.to_string(),
+            );
+            return false;
+        }
+    }
+    return true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_dir() {
+        let dir = std::env::temp_dir().join(format!("asc_copy_dir_{}", std::process::id()));
+        let source_dir = dir.join("ports").join("fmt");
+        std::fs::create_dir_all(source_dir.join("patches")).unwrap();
+        std::fs::write(source_dir.join("vcpkg.json"), "{}").unwrap();
+        std::fs::write(source_dir.join("patches").join("fix.patch"), "diff").unwrap();
+
+        let target_dir = dir.join("snapshots").join("fmt").join("11.0.2_0");
+        assert!(copy_dir(
+            &source_dir.to_string_lossy().replace(r"\", "/"),
+            &target_dir.to_string_lossy().replace(r"\", "/")
+        ));
+        assert_eq!(
+            std::fs::read_to_string(target_dir.join("vcpkg.json")).unwrap(),
+            "{}"
+        );
+        assert_eq!(
+            std::fs::read_to_string(target_dir.join("patches").join("fix.patch")).unwrap(),
+            "diff"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -34,3 +34,23 @@ pub fn get_file_name(path: &str) -> String {
         .unwrap()
         .to_string()
 }
+
+// resolve . and .. lexically, the path doesn't need to exist
+pub fn normalize_path(path: &str) -> String {
+    let path = path.replace(r"\", "/");
+    let mut parts: Vec<&str> = vec![];
+    for part in path.split("/") {
+        match part {
+            "." => {}
+            "" if !parts.is_empty() => {}
+            ".." if !parts.is_empty()
+                && parts.last() != Some(&"..")
+                && parts.last() != Some(&"") =>
+            {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
@@ -1,3 +1,4 @@
 pub mod fs;
+pub mod sanitizer;
 pub mod shell;
 pub mod str;
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use crate::errors::ErrorTag;
+
+pub static SANITIZERS: [&str; 5] = ["address", "undefined", "thread", "memory", "leak"];
+
+// sanitizers can't be linked into the same executable
+static CONFLICTS: [(&str, &str); 4] = [
+    ("address", "thread"),
+    ("address", "memory"),
+    ("thread", "memory"),
+    ("leak", "thread"),
+];
+
+// default runtime options, the environment variables set by users take precedence
+static RUNTIME_OPTIONS: [(&str, &str, &str); 5] = [
+    (
+        "address",
+        "ASAN_OPTIONS",
+        "abort_on_error=1:detect_stack_use_after_return=1",
+    ),
+    (
+        "undefined",
+        "UBSAN_OPTIONS",
+        "print_stacktrace=1:halt_on_error=1",
+    ),
+    (
+        "thread",
+        "TSAN_OPTIONS",
+        "second_deadlock_stack=1:halt_on_error=1",
+    ),
+    ("memory", "MSAN_OPTIONS", "poison_in_dtor=1:halt_on_error=1"),
+    ("leak", "LSAN_OPTIONS", "report_objects=1"),
+];
+
+// sort, deduplicate and check sanitizers
+pub fn normalize(sanitizers: &Vec<String>) -> Option<Vec<String>> {
+    let mut result = sanitizers
+        .iter()
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<String>>();
+    result.sort();
+    result.dedup();
+
+    for s in &result {
+        if !SANITIZERS.contains(&s.as_str()) {
+            tracing::error!(
+                error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                error_str = format!("unknown sanitizer {s}"),
+                sanitizers = SANITIZERS.join(", "),
+            );
+            return None;
+        }
+    }
+
+    for (a, b) in CONFLICTS {
+        if result.iter().any(|s| s == a) && result.iter().any(|s| s == b) {
+            tracing::error!(
+                error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                error_str = format!("sanitizer {a} can't be used together with {b}"),
+            );
+            return None;
+        }
+    }
+
+    return Some(result);
+}
+
+pub fn runtime_envs(sanitizers: &Vec<String>) -> HashMap<String, String> {
+    let mut envs = HashMap::new();
+    for (sanitizer, key, value) in RUNTIME_OPTIONS {
+        if !sanitizers.iter().any(|s| s == sanitizer) || std::env::var(key).is_ok() {
+            continue;
+        }
+        let mut value = value.to_string();
+        // leak detection was only supported on linux
+        if sanitizer == "address" && std::env::consts::OS == "linux" {
+            value.push_str(":detect_leaks=1");
+        }
+        envs.insert(key.to_string(), value);
+    }
+    return envs;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(&vec![
+                String::from("undefined"),
+                String::from("Address"),
+                String::from("undefined"),
+            ]),
+            Some(vec![String::from("address"), String::from("undefined")])
+        );
+        assert_eq!(
+            normalize(&vec![String::from("address"), String::from("thread")]),
+            None
+        );
+        assert_eq!(normalize(&vec![String::from("unknown")]), None);
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::cli::commands::VcpkgArgs;
 
 pub fn run(
@@ -7,9 +9,30 @@ pub fn run(
     capture_stdout: bool,
     capture_stderr: bool,
     silent: bool,
+) -> std::io::Result<std::process::Output> {
+    return run_with_envs(
+        command,
+        args,
+        work_dir,
+        &HashMap::new(),
+        capture_stdout,
+        capture_stderr,
+        silent,
+    );
+}
+
+pub fn run_with_envs(
+    command: &str,
+    args: &Vec<&str>,
+    work_dir: &str,
+    extra_envs: &HashMap<String, String>,
+    capture_stdout: bool,
+    capture_stderr: bool,
+    silent: bool,
 ) -> std::io::Result<std::process::Output> {
     let vcpkg_conf = VcpkgArgs::load_or_default();
-    let envs = vcpkg_conf.get_envs();
+    let mut envs = vcpkg_conf.get_envs();
+    envs.extend(extra_envs.clone());
 
     if !silent {
         tracing::info!(
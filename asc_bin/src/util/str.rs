@@ -45,6 +45,34 @@ pub fn longest_common_substring(s1: &str, s2: &str) -> String {
     s1[end_index - longest_length..end_index].to_string()
 }
 
+// optimal string alignment distance, adjacent transposition costs 1
+pub fn edit_distance(s1: &str, s2: &str) -> usize {
+    let a = s1.chars().collect::<Vec<char>>();
+    let b = s2.chars().collect::<Vec<char>>();
+    let (m, n) = (a.len(), b.len());
+
+    let mut d = vec![vec![0; n + 1]; m + 1];
+    for i in 0..=m {
+        d[i][0] = i;
+    }
+    for j in 0..=n {
+        d[0][j] = j;
+    }
+    for i in 1..=m {
+        for j in 1..=n {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[m][n]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +130,20 @@ mod tests {
             assert_eq!(common, expected);
         }
     }
+
+    #[test]
+    fn test_edit_distance() {
+        let test_cases = vec![
+            ("spdlog", "spdlog", 0),
+            ("spdlgo", "spdlog", 1),
+            ("sdplog", "spdlog", 1),
+            ("fmtt", "fmt", 1),
+            ("openssl", "opnsl", 2),
+            ("", "abc", 3),
+        ];
+
+        for (s1, s2, expected) in test_cases {
+            assert_eq!(edit_distance(s1, s2), expected);
+        }
+    }
 }
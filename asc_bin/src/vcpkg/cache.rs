@@ -0,0 +1,631 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::{
+    cli::commands::{CacheArgs, VcpkgArgs},
+    config::{project::LockConfig, relative_paths::ASC_LOCK_FILE_NAME},
+    errors::ErrorTag,
+    util,
+};
+
+static CONTROL_FILE_NAME: &str = "CONTROL";
+static SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+static BINARY_SOURCE_CLEAR: &str = "clear";
+static BINARY_SOURCE_DEFAULT: &str = "default";
+static BINARY_SOURCE_FILES: &str = "files";
+static BINARY_SOURCE_HTTP: &str = "http";
+static BINARY_SOURCE_ACCESS: [&str; 3] = ["read", "write", "readwrite"];
+
+// first paragraph of CONTROL in <cache>/<abi[0..2]>/<abi>.zip
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ControlInfo {
+    pub package: String,
+    pub version: String,
+    pub port_version: u32,
+    pub triplet: String,
+    pub abi: String,
+    // (port name, triplet), triplet was empty if it was not qualified
+    pub depends: Vec<(String, String)>,
+}
+
+impl ControlInfo {
+    pub fn format_version_text(&self) -> String {
+        if self.port_version == 0 {
+            self.version.clone()
+        } else {
+            format!("{}#{}", self.version, self.port_version)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedPackage {
+    path: String,
+    // <abi[0..2]>/<abi>.zip
+    name: String,
+    size: u64,
+    modified: std::time::SystemTime,
+    // default if CONTROL was unreadable
+    control: ControlInfo,
+}
+
+// vcpkg.toml without binary cache settings uses the default dir as vcpkg does
+fn load_vcpkg_args() -> VcpkgArgs {
+    let mut vcpkg_args = VcpkgArgs::load_or_default();
+    vcpkg_args.set_defaults();
+    return vcpkg_args;
+}
+
+pub fn size(_args: &CacheArgs) -> bool {
+    let vcpkg_args = load_vcpkg_args();
+
+    let mut sizes = BTreeMap::new();
+    let (mut total_count, mut total_size) = (0, 0);
+    for package in scan_cache(&cache_dirs(&vcpkg_args)) {
+        let key = if package.control.package.is_empty() {
+            (String::from("unknown"), String::new())
+        } else {
+            (package.control.package, package.control.triplet)
+        };
+        let entry = sizes.entry(key).or_insert((0, 0));
+        entry.0 += 1;
+        entry.1 += package.size;
+        total_count += 1;
+        total_size += package.size;
+    }
+
+    for ((port, triplet), (count, size)) in &sizes {
+        if triplet.is_empty() {
+            println!("{port}  {count} entries  {}", format_size(*size));
+        } else {
+            println!("{port}:{triplet}  {count} entries  {}", format_size(*size));
+        }
+    }
+    println!("total  {total_count} entries  {}", format_size(total_size));
+
+    return true;
+}
+
+pub fn prune(args: &CacheArgs) -> bool {
+    if args.days.is_none() && !args.unreferenced {
+        tracing::error!(
+            error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+            message = "asc cache prune --days=<N> and/or --unreferenced"
+        );
+        return false;
+    }
+
+    let vcpkg_args = load_vcpkg_args();
+    let packages = scan_cache(&cache_dirs(&vcpkg_args));
+
+    let referenced = if args.unreferenced {
+        match load_locked_versions(&args.lock) {
+            None => return false,
+            Some(locked) => Some(referenced_packages(&packages, &locked)),
+        }
+    } else {
+        None
+    };
+
+    let now = std::time::SystemTime::now();
+    let (mut count, mut size, mut has_error) = (0, 0, false);
+    for (index, package) in packages.iter().enumerate() {
+        // both conditions must match if both were given
+        if let Some(days) = args.days {
+            let age = now
+                .duration_since(package.modified)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            if age < days * SECONDS_PER_DAY {
+                continue;
+            }
+        }
+        if let Some(referenced) = &referenced {
+            if referenced.contains(&index) {
+                continue;
+            }
+        }
+
+        tracing::info!(
+            message = "prune",
+            port = package.control.package,
+            version = package.control.format_version_text(),
+            triplet = package.control.triplet,
+            path = package.path
+        );
+        if !args.dry_run {
+            if !util::fs::remove_file(&package.path) {
+                has_error = true;
+                continue;
+            }
+            // remove <abi[0..2]> if it was empty
+            util::fs::remove_dir(&util::fs::get_parent_dir(&package.path));
+        }
+        count += 1;
+        size += package.size;
+    }
+
+    println!(
+        "{} {count} entries  {}",
+        if args.dry_run {
+            "would prune"
+        } else {
+            "pruned"
+        },
+        format_size(size)
+    );
+
+    return !has_error;
+}
+
+pub fn export(args: &CacheArgs) -> bool {
+    let path = match &args.path {
+        None => {
+            tracing::error!(
+                error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                message = "asc cache export --path=<archive.zip>"
+            );
+            return false;
+        }
+        Some(path) => path,
+    };
+
+    let vcpkg_args = load_vcpkg_args();
+    let packages = scan_cache(&cache_dirs(&vcpkg_args));
+    let referenced = if args.lock.is_empty() {
+        None
+    } else {
+        match load_locked_versions(&args.lock) {
+            None => return false,
+            Some(locked) => Some(referenced_packages(&packages, &locked)),
+        }
+    };
+
+    let file = match std::fs::File::create(path) {
+        Err(e) => {
+            tracing::error!(
+                call = "std::fs::File::create",
+                path = path,
+                error_tag = ErrorTag::WriteFileError.as_ref(),
+                error_str = e.to_string()
+            );
+            return false;
+        }
+        Ok(f) => f,
+    };
+
+    // cached packages were zip already
+    let mut writer = zip::ZipWriter::new(file);
+    let mut names = BTreeSet::new();
+    for (index, package) in packages.iter().enumerate() {
+        if let Some(referenced) = &referenced {
+            if !referenced.contains(&index) {
+                continue;
+            }
+        }
+        // same abi in multiple cache dirs
+        if !names.insert(package.name.clone()) {
+            continue;
+        }
+
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored)
+            .large_file(package.size >= u32::MAX as u64);
+        let result = writer
+            .start_file(package.name.as_str(), options)
+            .map_err(|e| e.to_string())
+            .and_then(|_| std::fs::File::open(&package.path).map_err(|e| e.to_string()))
+            .and_then(|mut f| std::io::copy(&mut f, &mut writer).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::error!(
+                call = "zip::ZipWriter::start_file",
+                path = package.path,
+                error_tag = ErrorTag::WriteFileError.as_ref(),
+                error_str = e
+            );
+            return false;
+        }
+    }
+    if let Err(e) = writer.finish() {
+        tracing::error!(
+            call = "zip::ZipWriter::finish",
+            path = path,
+            error_tag = ErrorTag::WriteFileError.as_ref(),
+            error_str = e.to_string()
+        );
+        return false;
+    }
+
+    println!("exported {} entries to {path}", names.len());
+    return true;
+}
+
+pub fn import(args: &CacheArgs) -> bool {
+    let path = match &args.path {
+        None => {
+            tracing::error!(
+                error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                message = "asc cache import --path=<archive.zip>"
+            );
+            return false;
+        }
+        Some(path) => path,
+    };
+
+    let mut archive = match std::fs::File::open(path)
+        .map_err(|e| e.to_string())
+        .and_then(|f| zip::ZipArchive::new(f).map_err(|e| e.to_string()))
+    {
+        Err(e) => {
+            tracing::error!(
+                call = "zip::ZipArchive::new",
+                path = path,
+                error_tag = ErrorTag::ReadFileError.as_ref(),
+                error_str = e
+            );
+            return false;
+        }
+        Ok(a) => a,
+    };
+
+    let vcpkg_args = load_vcpkg_args();
+    let cache_dir = vcpkg_args
+        .env_default_binary_cache
+        .clone()
+        .unwrap_or_default();
+    if cache_dir.is_empty() {
+        tracing::error!(
+            func = "vcpkg_args.env_default_binary_cache",
+            error_tag = ErrorTag::InvalidConfigError.as_ref(),
+            message = "binary cache dir was empty in vcpkg.toml"
+        );
+        return false;
+    }
+
+    let (mut imported, mut skipped) = (0, 0);
+    for index in 0..archive.len() {
+        let mut entry = match archive.by_index(index) {
+            Err(e) => {
+                tracing::error!(
+                    call = "zip::ZipArchive::by_index",
+                    path = path,
+                    error_tag = ErrorTag::ReadFileError.as_ref(),
+                    error_str = e.to_string()
+                );
+                return false;
+            }
+            Ok(entry) => entry,
+        };
+        // reject absolute and ../ names
+        let name = match entry.enclosed_name() {
+            Some(name) if entry.is_file() => name.to_string_lossy().replace(r"\", "/"),
+            _ => continue,
+        };
+        if !name.ends_with(".zip") {
+            continue;
+        }
+
+        let target = format!("{cache_dir}/{name}");
+        if util::fs::is_file_exists(&target) {
+            skipped += 1;
+            continue;
+        }
+        util::fs::create_dirs(&util::fs::get_parent_dir(&target));
+        if let Err(e) =
+            std::fs::File::create(&target).and_then(|mut f| std::io::copy(&mut entry, &mut f))
+        {
+            tracing::error!(
+                call = "std::io::copy",
+                path = target,
+                error_tag = ErrorTag::WriteFileError.as_ref(),
+                error_str = e.to_string()
+            );
+            util::fs::remove_file(&target);
+            return false;
+        }
+        imported += 1;
+    }
+
+    println!("imported {imported} entries, skipped {skipped} existing entries into {cache_dir}");
+    return true;
+}
+
+// VCPKG_BINARY_SOURCES, sources separated by ';', source args separated by ','
+pub fn validate_binary_sources(sources: &str) -> bool {
+    for source in sources.split(';').filter(|s| !s.trim().is_empty()) {
+        let parts = source.split(',').map(|s| s.trim()).collect::<Vec<&str>>();
+        let valid = match parts[0] {
+            kind if kind == BINARY_SOURCE_CLEAR => parts.len() == 1,
+            // default[,<path>][,<rw>]
+            kind if kind == BINARY_SOURCE_DEFAULT => {
+                parts.len() <= 3 && parts[1..].iter().all(|p| !p.is_empty())
+            }
+            // files,<absolute path>[,<rw>]
+            kind if kind == BINARY_SOURCE_FILES => {
+                (parts.len() == 2 || parts.len() == 3)
+                    && std::path::Path::new(parts[1]).is_absolute()
+                    && (parts.len() == 2 || BINARY_SOURCE_ACCESS.contains(&parts[2]))
+            }
+            // http,<url template>[,<rw>[,<header>]]
+            kind if kind == BINARY_SOURCE_HTTP => {
+                (2..=4).contains(&parts.len())
+                    && url::Url::parse(parts[1])
+                        .map(|u| u.scheme() == "http" || u.scheme() == "https")
+                        .unwrap_or(false)
+                    && (parts.len() == 2 || BINARY_SOURCE_ACCESS.contains(&parts[2]))
+            }
+            kind => {
+                tracing::warn!("binary source {kind} was not checked by asc");
+                true
+            }
+        };
+        if !valid {
+            tracing::error!(
+                error_tag = ErrorTag::InvalidConfigError.as_ref(),
+                source = source,
+                message = "files,<absolute dir>[,read|write|readwrite] or http,<url>[,read|write|readwrite[,<header>]]"
+            );
+            return false;
+        }
+    }
+    return true;
+}
+
+pub fn files_provider_dirs(sources: &str) -> Vec<String> {
+    let mut dirs = vec![];
+    for source in sources.split(';') {
+        let parts = source.split(',').map(|s| s.trim()).collect::<Vec<&str>>();
+        if parts[0] == BINARY_SOURCE_FILES && parts.len() > 1 && !parts[1].is_empty() {
+            dirs.push(parts[1].replace(r"\", "/"));
+        }
+    }
+    return dirs;
+}
+
+// default binary cache and files providers, in the order vcpkg reads them
+fn cache_dirs(vcpkg_args: &VcpkgArgs) -> Vec<String> {
+    let default_dir = vcpkg_args
+        .env_default_binary_cache
+        .clone()
+        .unwrap_or_default();
+
+    let mut dirs = vec![default_dir.clone()];
+    if let Some(sources) = &vcpkg_args.env_binary_sources {
+        for source in sources.split(';') {
+            let parts = source.split(',').map(|s| s.trim()).collect::<Vec<&str>>();
+            match parts[0] {
+                kind if kind == BINARY_SOURCE_CLEAR => dirs.clear(),
+                kind if kind == BINARY_SOURCE_DEFAULT => match parts.get(1) {
+                    Some(dir) if !BINARY_SOURCE_ACCESS.contains(dir) => {
+                        dirs.push(dir.replace(r"\", "/"))
+                    }
+                    _ => dirs.push(default_dir.clone()),
+                },
+                _ => dirs.extend(files_provider_dirs(source)),
+            }
+        }
+    }
+
+    let mut unique = BTreeSet::new();
+    dirs.retain(|d| !d.is_empty() && unique.insert(d.clone()));
+    return dirs;
+}
+
+fn scan_cache(dirs: &Vec<String>) -> Vec<CachedPackage> {
+    let mut packages = vec![];
+    for dir in dirs {
+        for entry in walkdir::WalkDir::new(dir)
+            .min_depth(2)
+            .max_depth(2)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path().to_string_lossy().replace(r"\", "/");
+            if !path.ends_with(".zip") {
+                continue;
+            }
+            let metadata = match entry.metadata() {
+                Err(_) => continue,
+                Ok(m) => m,
+            };
+
+            let control = read_control(&path)
+                .and_then(|text| parse_control(&text))
+                .unwrap_or_else(|| {
+                    tracing::warn!("can't read {CONTROL_FILE_NAME} from {path}");
+                    ControlInfo::default()
+                });
+            packages.push(CachedPackage {
+                name: util::fs::remove_prefix(&path, dir, &String::new())
+                    .trim_start_matches('/')
+                    .to_string(),
+                path,
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(std::time::UNIX_EPOCH),
+                control,
+            });
+        }
+    }
+    return packages;
+}
+
+fn read_control(path: &str) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+    let mut control = archive.by_name(CONTROL_FILE_NAME).ok()?;
+    let mut text = String::new();
+    std::io::Read::read_to_string(&mut control, &mut text).ok()?;
+    return Some(text);
+}
+
+pub fn parse_control(text: &str) -> Option<ControlInfo> {
+    let mut info = ControlInfo::default();
+    // feature paragraphs follow the core paragraph
+    for line in text.lines().take_while(|l| !l.trim().is_empty()) {
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            match key.trim() {
+                "Package" => info.package = value.to_string(),
+                "Version" => info.version = value.to_string(),
+                "Port-Version" => info.port_version = value.parse().unwrap_or(0),
+                "Architecture" => info.triplet = value.to_string(),
+                "Abi" => info.abi = value.to_string(),
+                "Depends" => {
+                    for dep in value.split(',').map(|d| d.trim()).filter(|d| !d.is_empty()) {
+                        // name[features]:triplet
+                        let (name, triplet) = dep.split_once(':').unwrap_or((dep, ""));
+                        let name = name.split(['[', '(', ' ']).next().unwrap_or(name);
+                        info.depends
+                            .push((name.to_string(), triplet.trim().to_string()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if info.package.is_empty() {
+        return None;
+    }
+    return Some(info);
+}
+
+// port name -> locked version texts
+fn load_locked_versions(lock_paths: &Vec<String>) -> Option<BTreeMap<String, BTreeSet<String>>> {
+    let default_paths = vec![ASC_LOCK_FILE_NAME.to_string()];
+    let lock_paths = if lock_paths.is_empty() {
+        &default_paths
+    } else {
+        lock_paths
+    };
+
+    let mut locked = BTreeMap::new();
+    for path in lock_paths {
+        match LockConfig::load(path, false) {
+            None => {
+                tracing::error!(
+                    call = "LockConfig::load",
+                    path = path,
+                    error_tag = ErrorTag::FileNotFoundError.as_ref(),
+                );
+                return None;
+            }
+            Some(lock) => {
+                for (name, package) in lock.packages {
+                    locked
+                        .entry(name)
+                        .or_insert_with(BTreeSet::new)
+                        .insert(package.format_version_text());
+                }
+            }
+        }
+    }
+    return Some(locked);
+}
+
+// indexes of locked packages and their cached transitive dependencies
+fn referenced_packages(
+    packages: &Vec<CachedPackage>,
+    locked: &BTreeMap<String, BTreeSet<String>>,
+) -> BTreeSet<usize> {
+    let mut referenced = BTreeSet::new();
+    let mut queue = VecDeque::new();
+    for (index, package) in packages.iter().enumerate() {
+        if let Some(versions) = locked.get(&package.control.package) {
+            if versions.contains(&package.control.format_version_text()) {
+                referenced.insert(index);
+                queue.push_back(index);
+            }
+        }
+    }
+
+    // dependency versions were not recorded in CONTROL, keep every cached version
+    while let Some(index) = queue.pop_front() {
+        let control = &packages[index].control;
+        for (name, triplet) in &control.depends {
+            let triplet = if triplet.is_empty() {
+                &control.triplet
+            } else {
+                triplet
+            };
+            for (dep_index, dep) in packages.iter().enumerate() {
+                if &dep.control.package == name
+                    && &dep.control.triplet == triplet
+                    && referenced.insert(dep_index)
+                {
+                    queue.push_back(dep_index);
+                }
+            }
+        }
+    }
+
+    return referenced;
+}
+
+fn format_size(size: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        return format!("{size} {}", units[unit]);
+    }
+    return format!("{value:.1} {}", units[unit]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(control: &str) -> CachedPackage {
+        CachedPackage {
+            path: String::new(),
+            name: String::new(),
+            size: 0,
+            modified: std::time::UNIX_EPOCH,
+            control: parse_control(control).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_binary_cache() {
+        let fmt = cached(
+            "Package: fmt\nVersion: 11.0.2\nPort-Version: 1\nDepends: vcpkg-cmake:x64-linux, vcpkg-cmake-config:x64-linux\nArchitecture: x64-linux\nAbi: 1234\n\nPackage: fmt\nFeature: extra\nDepends: zlib\n",
+        );
+        assert_eq!(fmt.control.format_version_text(), "11.0.2#1");
+        assert_eq!(fmt.control.triplet, "x64-linux");
+        assert_eq!(fmt.control.depends.len(), 2);
+
+        let packages = vec![
+            fmt,
+            cached("Package: vcpkg-cmake\nVersion: 2024-04-23\nArchitecture: x64-linux\n"),
+            cached("Package: vcpkg-cmake-config\nVersion: 2024-05-23\nArchitecture: x64-linux\n"),
+            cached("Package: zlib\nVersion: 1.3.1\nArchitecture: x64-linux\n"),
+            cached("Package: fmt\nVersion: 10.2.1\nArchitecture: x64-linux\n"),
+        ];
+        let locked = BTreeMap::from([(
+            String::from("fmt"),
+            BTreeSet::from([String::from("11.0.2#1")]),
+        )]);
+        assert_eq!(
+            referenced_packages(&packages, &locked),
+            BTreeSet::from([0, 1, 2])
+        );
+
+        assert!(validate_binary_sources(
+            "clear;files,/var/cache/vcpkg,readwrite;http,https://cache.example.com/{sha}.zip,read"
+        ));
+        assert!(!validate_binary_sources("files,relative/dir"));
+        assert!(!validate_binary_sources(
+            "http,ftp://cache.example.com/{sha}.zip"
+        ));
+        assert_eq!(
+            files_provider_dirs("default;files,/var/cache/vcpkg,read"),
+            vec![String::from("/var/cache/vcpkg")]
+        );
+    }
+}
@@ -1,9 +1,15 @@
 use crate::cli::commands::VcpkgArgs;
 
-use super::VcpkgManager;
+use super::{cache, VcpkgManager};
 
 impl VcpkgManager {
     pub fn config_set(&self) -> bool {
+        if let Some(sources) = &self.args.env_binary_sources {
+            if !cache::validate_binary_sources(sources) {
+                return false;
+            }
+        }
+
         // write conf to file
         let mut conf = VcpkgArgs::load_or_default();
         return conf.update(&self.args, true, true);
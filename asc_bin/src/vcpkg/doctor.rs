@@ -0,0 +1,388 @@
+use crate::{
+    cli::commands::VcpkgArgs,
+    config::{
+        system_paths::DataPath,
+        vcpkg::{versions_baseline::VcpkgBaseline, versions_port::VcpkgPortVersions},
+    },
+    errors::ErrorTag,
+    git::{self, log::GitCommitInfo},
+    util,
+};
+
+use super::{
+    index::{VcpkgGitTreeIndex, VcpkgSearchIndex},
+    search, VcpkgManager,
+};
+
+// how a git registry clone was repaired
+#[derive(Clone, Debug, PartialEq)]
+enum Refetch {
+    // missing or HEAD was not resolved
+    Clone,
+    // wrong branch or invalid versions files
+    Reset,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum IndexState {
+    Missing,
+    // check-point was behind the latest commit
+    Stale,
+    // check-point was not in history of the branch, rebuild from scratch
+    Obsolete,
+}
+
+impl VcpkgManager {
+    // check registry clones, versions files and indexes, then ask to re-fetch and reindex
+    pub fn doctor(&mut self) -> bool {
+        self.config_get(true);
+
+        let mut refetches = vec![];
+        let mut indexes = vec![];
+        for (name, url, branch, directory) in self.args.flatten_registry() {
+            let filesystem = VcpkgArgs::is_filesystem_url(&url);
+            tracing::info!(message = "doctor", registry = name, path = directory);
+
+            if !util::fs::is_dir_exists(&directory) {
+                tracing::error!(
+                    registry = name,
+                    path = directory,
+                    error_tag = ErrorTag::DirectoryNotFoundError.as_ref(),
+                );
+                if !filesystem {
+                    refetches.push((name, url, branch, directory, Refetch::Clone));
+                }
+                continue;
+            }
+
+            if !filesystem {
+                match git::rev_parse::abbrev_ref(&directory) {
+                    None => {
+                        tracing::error!(
+                            registry = name,
+                            path = directory,
+                            message = "HEAD was not resolved, the clone may be partially fetched"
+                        );
+                        refetches.push((name, url, branch, directory, Refetch::Clone));
+                        continue;
+                    }
+                    Some(head) => {
+                        if !branch.is_empty() && head != branch {
+                            tracing::error!(
+                                registry = name,
+                                branch = branch,
+                                head = head,
+                                message = "registry was not on the configured branch"
+                            );
+                            refetches.push((
+                                name.clone(),
+                                url.clone(),
+                                branch.clone(),
+                                directory.clone(),
+                                Refetch::Reset,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            if !Self::check_versions(&name, &directory, filesystem)
+                && !filesystem
+                && !refetches.iter().any(|r| r.0 == name)
+            {
+                refetches.push((
+                    name.clone(),
+                    url.clone(),
+                    branch.clone(),
+                    directory.clone(),
+                    Refetch::Reset,
+                ));
+            }
+
+            // filesystem registry was reindexed every time
+            if !filesystem {
+                if let Some(state) = self.check_index(&name, &directory) {
+                    indexes.push((name, state));
+                }
+            }
+        }
+
+        if refetches.is_empty() && indexes.is_empty() {
+            println!("registries were healthy");
+            return true;
+        }
+
+        let mut result = true;
+        if !refetches.is_empty() {
+            println!(
+                "Do you want to re-fetch {}, yes or no? ",
+                refetches
+                    .iter()
+                    .map(|r| r.0.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+            if !Self::confirm() {
+                return false;
+            }
+            for (name, url, branch, directory, refetch) in &refetches {
+                result &= Self::refetch(name, url, branch, directory, refetch);
+                // index check-point of re-cloned registry may be unreachable
+                if !indexes.iter().any(|i| &i.0 == name) {
+                    indexes.push((name.clone(), IndexState::Stale));
+                }
+            }
+        }
+
+        println!(
+            "Do you want to reindex {}, yes or no? ",
+            indexes
+                .iter()
+                .map(|i| i.0.clone())
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        if !Self::confirm() {
+            return false;
+        }
+        let index_dir = self.args.index_directory.clone().unwrap();
+        for (name, state) in &indexes {
+            if state == &IndexState::Obsolete {
+                for path in [
+                    DataPath::vcpkg_tree_index_bin(&index_dir, name),
+                    DataPath::vcpkg_tree_index_json(&index_dir, name),
+                    DataPath::vcpkg_search_index_bin(&index_dir, name),
+                    DataPath::vcpkg_search_index_json(&index_dir, name),
+                ] {
+                    if util::fs::is_file_exists(&path) {
+                        result &= util::fs::remove_file(&path);
+                    }
+                }
+            }
+        }
+        result &= self.index();
+
+        return result;
+    }
+
+    // versions/baseline.json and versions/<x>-/<port>.json of baseline versions
+    fn check_versions(name: &str, directory: &str, filesystem: bool) -> bool {
+        let baseline_path = DataPath::vcpkg_versions_baseline_json_path(directory);
+        if !util::fs::is_file_exists(&baseline_path) {
+            tracing::error!(
+                registry = name,
+                path = baseline_path,
+                error_tag = ErrorTag::FileNotFoundError.as_ref(),
+            );
+            return false;
+        }
+        let baseline = match VcpkgBaseline::load(&baseline_path, false) {
+            None => {
+                tracing::error!(
+                    registry = name,
+                    path = baseline_path,
+                    error_tag = ErrorTag::JsonDeserializeError.as_ref(),
+                );
+                return false;
+            }
+            Some(baseline) => baseline,
+        };
+
+        let mut errors = 0;
+        for (port_name, baseline_version) in &baseline.default {
+            let versions_path = DataPath::vcpkg_versions_port_json_path(directory, port_name);
+            let version_text = baseline_version.format_version_text();
+            let error_tag = if !util::fs::is_file_exists(&versions_path) {
+                Some(ErrorTag::FileNotFoundError)
+            } else {
+                match VcpkgPortVersions::load(&versions_path, false) {
+                    None => Some(ErrorTag::JsonDeserializeError),
+                    Some(versions) => match versions
+                        .versions
+                        .iter()
+                        .find(|v| v.format_version_text() == version_text)
+                    {
+                        None => Some(ErrorTag::VcpkgPortVersionNotFound),
+                        Some(v) if filesystem => {
+                            if util::fs::is_dir_exists(&search::get_port_version_dir(
+                                directory, &v.path,
+                            )) {
+                                None
+                            } else {
+                                Some(ErrorTag::DirectoryNotFoundError)
+                            }
+                        }
+                        Some(v) if v.git_tree.is_empty() => Some(ErrorTag::InvalidConfigError),
+                        Some(_) => None,
+                    },
+                }
+            };
+            if let Some(error_tag) = error_tag {
+                tracing::error!(
+                    registry = name,
+                    port = port_name,
+                    version = version_text,
+                    path = versions_path,
+                    error_tag = error_tag.as_ref(),
+                );
+                errors += 1;
+            }
+        }
+
+        tracing::info!(
+            registry = name,
+            ports = baseline.default.len(),
+            errors = errors,
+            "check versions"
+        );
+        return errors == 0;
+    }
+
+    // git tree and search indexes against the latest commit
+    fn check_index(&self, name: &str, directory: &str) -> Option<IndexState> {
+        let index_dir = self.args.index_directory.clone().unwrap();
+        let latest_commit = Self::get_latest_commit(directory);
+
+        let check_points = [
+            (
+                "git tree index",
+                VcpkgGitTreeIndex::load_index(&index_dir, name).map(|i| i.check_point().clone()),
+            ),
+            (
+                "search index",
+                VcpkgSearchIndex::load_index(&index_dir, name).map(|i| i.check_point().clone()),
+            ),
+        ];
+
+        let mut state = None;
+        for (kind, check_point) in check_points {
+            let current = Self::check_point_state(directory, &latest_commit, check_point);
+            if let Some(current) = &current {
+                tracing::warn!(
+                    registry = name,
+                    index = kind,
+                    latest_commit = latest_commit.hash,
+                    state = format!("{current:?}")
+                );
+            }
+            // obsolete > missing > stale
+            state = match (state, current) {
+                (Some(IndexState::Obsolete), _) | (_, Some(IndexState::Obsolete)) => {
+                    Some(IndexState::Obsolete)
+                }
+                (Some(IndexState::Missing), _) | (_, Some(IndexState::Missing)) => {
+                    Some(IndexState::Missing)
+                }
+                (s, c) => s.or(c),
+            };
+        }
+        return state;
+    }
+
+    fn check_point_state(
+        directory: &str,
+        latest_commit: &GitCommitInfo,
+        check_point: Option<GitCommitInfo>,
+    ) -> Option<IndexState> {
+        match check_point {
+            None => Some(IndexState::Missing),
+            Some(c) if c.hash == latest_commit.hash => None,
+            Some(c) if c.hash.is_empty() => Some(IndexState::Stale),
+            Some(c) => {
+                if git::merge_base::is_ancestor(directory, &c.hash, &latest_commit.hash) {
+                    Some(IndexState::Stale)
+                } else {
+                    Some(IndexState::Obsolete)
+                }
+            }
+        }
+    }
+
+    fn refetch(name: &str, url: &str, branch: &str, directory: &str, refetch: &Refetch) -> bool {
+        tracing::info!(
+            message = "re-fetch",
+            registry = name,
+            how = format!("{refetch:?}")
+        );
+        match refetch {
+            Refetch::Clone => {
+                if util::fs::is_dir_exists(directory) && !util::fs::remove_dirs(directory) {
+                    return false;
+                }
+                return git::clone::run(url, branch, directory, &vec![]);
+            }
+            Refetch::Reset => {
+                let mut result = git::fetch::run(directory);
+                if !branch.is_empty() {
+                    result &= git::checkout::branch(directory, branch);
+                }
+                // default branch of remote if branch was omitted
+                result &= git::reset::run(
+                    directory,
+                    branch,
+                    if branch.is_empty() { "origin/HEAD" } else { "" },
+                );
+                return result;
+            }
+        }
+    }
+
+    fn confirm() -> bool {
+        let mut choose = String::new();
+        std::io::stdin().read_line(&mut choose).unwrap();
+        return [String::from("y"), String::from("yes")].contains(&choose.trim().to_lowercase());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_versions() {
+        let dir = search::write_filesystem_registry("asc_doctor");
+        let directory = dir.to_string_lossy().replace(r"\", "/");
+        std::fs::remove_file(dir.join("versions").join("baseline.json")).unwrap();
+
+        // baseline.json was not found
+        assert!(!VcpkgManager::check_versions("local", &directory, true));
+
+        std::fs::write(
+            dir.join("versions").join("baseline.json"),
+            r#"{"default": {"fmt": {"baseline": "11.0.2", "port-version": 0}}}"#,
+        )
+        .unwrap();
+        assert!(VcpkgManager::check_versions("local", &directory, true));
+
+        // truncated baseline file
+        std::fs::write(
+            dir.join("versions").join("baseline.json"),
+            r#"{"default": {"fmt": {"baseline": "11.0.2""#,
+        )
+        .unwrap();
+        assert!(!VcpkgManager::check_versions("local", &directory, true));
+
+        // baseline version was not in versions/f-/fmt.json
+        std::fs::write(
+            dir.join("versions").join("baseline.json"),
+            r#"{"default": {"fmt": {"baseline": "11.0.2", "port-version": 1}}}"#,
+        )
+        .unwrap();
+        assert!(!VcpkgManager::check_versions("local", &directory, true));
+
+        // truncated versions file
+        std::fs::write(
+            dir.join("versions").join("baseline.json"),
+            r#"{"default": {"fmt": {"baseline": "11.0.2", "port-version": 0}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("versions").join("f-").join("fmt.json"),
+            r#"{"versions": [{"version": "11.0.2""#,
+        )
+        .unwrap();
+        assert!(!VcpkgManager::check_versions("local", &directory, true));
+
+        std::fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+}
@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, OnceLock},
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -6,13 +9,18 @@ use basic_trie::DataTrie;
 use config_file_derives::ConfigFile;
 use config_file_types;
 
-use super::VcpkgManager;
+use super::{index_file, VcpkgManager};
 
 use crate::{
     cli::commands::VcpkgArgs,
     config::{
         self,
+        relative_paths::{
+            VCPKG_BASELINE_JSON_FILE_NAME, VCPKG_CONTROL_FILE_NAME, VCPKG_JSON_FILE_NAME,
+            VCPKG_PORTS_DIR_NAME, VCPKG_VERSIONS_DIR_NAME,
+        },
         vcpkg::{
+            port_manifest::VcpkgPortManifest,
             versions_baseline::VcpkgBaseline,
             versions_port::{VcpkgGitTreeInfo, VcpkgPortVersions},
         },
@@ -28,14 +36,29 @@ pub struct VcpkgSearchIndex {
     #[serde(skip)]
     path: String,
 
-    pub prefix_trie: DataTrie<String>,
-    pub postfix_trie: DataTrie<String>,
+    // built from baseline on first wildcard query instead of being stored
+    #[serde(skip)]
+    prefix_trie: OnceLock<DataTrie<String>>,
+    #[serde(skip)]
+    postfix_trie: OnceLock<DataTrie<String>>,
 
     pub baseline: VcpkgBaseline,
 
+    // searched by description, homepage and feature names
+    #[serde(default)]
+    pub summaries: BTreeMap<String, VcpkgPortSummary>,
+
     check_point: GitCommitInfo,
 }
 
+// asc
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct VcpkgPortSummary {
+    pub description: String,
+    pub homepage: String,
+    pub features: Vec<String>,
+}
+
 // asc
 #[derive(Clone, Debug, Default, Deserialize, Serialize, ConfigFile)]
 #[config_file_ext("json")]
@@ -48,6 +71,69 @@ pub struct VcpkgGitTreeIndex {
     check_point: GitCommitInfo,
 }
 
+// json files were loaded for migration only, see index_file
+impl VcpkgSearchIndex {
+    pub fn load_index(index_dir: &str, registry: &str) -> Option<Arc<Self>> {
+        return index_file::load_or_migrate(
+            &config::system_paths::DataPath::vcpkg_search_index_bin(index_dir, registry),
+            &config::system_paths::DataPath::vcpkg_search_index_json(index_dir, registry),
+            |path| Self::load(path, false),
+        );
+    }
+
+    pub fn dump_index(&self, index_dir: &str, registry: &str) -> bool {
+        return index_file::dump(
+            self,
+            &config::system_paths::DataPath::vcpkg_search_index_bin(index_dir, registry),
+        );
+    }
+
+    pub fn check_point(&self) -> &GitCommitInfo {
+        return &self.check_point;
+    }
+
+    pub fn prefix_trie(&self) -> &DataTrie<String> {
+        self.prefix_trie.get_or_init(|| {
+            let mut trie = DataTrie::new();
+            for port_name in self.baseline.default.keys() {
+                trie.insert(port_name, port_name.clone());
+            }
+            trie
+        })
+    }
+
+    pub fn postfix_trie(&self) -> &DataTrie<String> {
+        self.postfix_trie.get_or_init(|| {
+            let mut trie = DataTrie::new();
+            for port_name in self.baseline.default.keys() {
+                trie.insert(&util::str::reverse_string(port_name), port_name.clone());
+            }
+            trie
+        })
+    }
+}
+
+impl VcpkgGitTreeIndex {
+    pub fn load_index(index_dir: &str, registry: &str) -> Option<Arc<Self>> {
+        return index_file::load_or_migrate(
+            &config::system_paths::DataPath::vcpkg_tree_index_bin(index_dir, registry),
+            &config::system_paths::DataPath::vcpkg_tree_index_json(index_dir, registry),
+            |path| Self::load(path, false),
+        );
+    }
+
+    pub fn dump_index(&self, index_dir: &str, registry: &str) -> bool {
+        return index_file::dump(
+            self,
+            &config::system_paths::DataPath::vcpkg_tree_index_bin(index_dir, registry),
+        );
+    }
+
+    pub fn check_point(&self) -> &GitCommitInfo {
+        return &self.check_point;
+    }
+}
+
 impl VcpkgManager {
     pub fn index(&mut self) -> bool {
         self.config_get(true);
@@ -81,12 +167,14 @@ impl VcpkgManager {
         let versions_port_json_path =
             config::system_paths::DataPath::vcpkg_versions_port_json_path(&vcpkg_root_dir, port);
         if let Some(versions) = VcpkgPortVersions::load(&versions_port_json_path, true) {
-            if let Some(git_tree_index) = VcpkgGitTreeIndex::load(
-                &config::system_paths::DataPath::vcpkg_tree_index_json(
-                    self.args.index_directory.as_ref().unwrap(),
-                    &registry,
-                ),
-                false,
+            // filesystem registry has no commits
+            if self.args.is_filesystem_registry(registry) {
+                for v in versions.versions {
+                    results.push((v.format_version_text(), String::new(), String::new()));
+                }
+            } else if let Some(git_tree_index) = VcpkgGitTreeIndex::load_index(
+                self.args.index_directory.as_ref().unwrap(),
+                &registry,
             ) {
                 for v in versions.versions {
                     if let Some(info) = git_tree_index.index.get(&v.git_tree) {
@@ -107,99 +195,210 @@ impl VcpkgManager {
 
     fn build_search_index(&mut self) -> bool {
         for (name, vcpkg_root_dir) in Self::get_vcpkg_root_dir() {
-            let commits = self.get_commits(&vcpkg_root_dir);
-            let latest_commit = &commits[commits.len() - 1];
+            // filesystem registry has no history, it was reindexed every time
+            let filesystem = self.args.is_filesystem_registry(&name);
+            let latest_commit = &if filesystem {
+                GitCommitInfo::default()
+            } else {
+                Self::get_latest_commit(&vcpkg_root_dir)
+            };
 
             let versions_baseline_json_path =
                 config::system_paths::DataPath::vcpkg_versions_baseline_json_path(&vcpkg_root_dir);
             match VcpkgBaseline::load(&versions_baseline_json_path, false) {
                 None => return false,
                 Some(baseline_data) => {
-                    let mut search_index = VcpkgSearchIndex::load(
-                        &config::system_paths::DataPath::vcpkg_search_index_json(
-                            self.args.index_directory.as_ref().unwrap(),
-                            &name,
-                        ),
-                        true,
-                    )
-                    .unwrap();
-                    if latest_commit.date_time < search_index.check_point.date_time {
-                        continue;
-                    }
-                    if latest_commit.hash == search_index.check_point.hash {
-                        continue;
+                    let index_dir = self.args.index_directory.clone().unwrap();
+                    let mut search_index = VcpkgSearchIndex::load_index(&index_dir, &name)
+                        .map(|index| index.as_ref().clone())
+                        .unwrap_or_default();
+                    if !filesystem {
+                        if latest_commit.date_time < search_index.check_point.date_time {
+                            continue;
+                        }
+                        // rebuild index without summaries
+                        if latest_commit.hash == search_index.check_point.hash
+                            && !search_index.summaries.is_empty()
+                        {
+                            continue;
+                        }
                     }
 
+                    search_index.summaries.clear();
                     for port_name in baseline_data.default.keys() {
-                        search_index
-                            .prefix_trie
-                            .insert(&port_name, port_name.clone());
-                        search_index
-                            .postfix_trie
-                            .insert(&util::str::reverse_string(port_name), port_name.clone());
+                        if let Some(summary) = Self::get_port_summary(&vcpkg_root_dir, port_name) {
+                            search_index.summaries.insert(port_name.clone(), summary);
+                        }
                     }
                     search_index.baseline = baseline_data;
                     search_index.check_point = latest_commit.clone();
-                    search_index.dump(false, false);
+                    search_index.dump_index(&index_dir, &name);
                 }
             }
         }
         return true;
     }
 
+    // vcpkg.json or CONTROL of port in the working tree
+    fn get_port_summary(vcpkg_root_dir: &str, port_name: &str) -> Option<VcpkgPortSummary> {
+        let mut port_dir =
+            config::system_paths::DataPath::vcpkg_ports_dir_path(vcpkg_root_dir, port_name);
+        if !util::fs::is_dir_exists(&port_dir) {
+            // filesystem registry without ports/, use path of the latest version
+            let versions = VcpkgPortVersions::load(
+                &config::system_paths::DataPath::vcpkg_versions_port_json_path(
+                    vcpkg_root_dir,
+                    port_name,
+                ),
+                true,
+            )?;
+            port_dir = super::search::get_port_version_dir(
+                vcpkg_root_dir,
+                &versions.versions.first()?.path,
+            );
+        }
+        let mut path = format!("{port_dir}/{VCPKG_JSON_FILE_NAME}");
+        if !util::fs::is_file_exists(&path) {
+            path = format!("{port_dir}/{VCPKG_CONTROL_FILE_NAME}");
+        }
+        let manifest = VcpkgPortManifest::from_file(&path)?;
+        return Some(VcpkgPortSummary {
+            description: manifest.description.join(" "),
+            homepage: manifest.homepage,
+            features: manifest.features.keys().cloned().collect(),
+        });
+    }
+
     fn build_git_tree_index(&mut self) {
         for (name, vcpkg_root_dir) in Self::get_vcpkg_root_dir() {
-            let commits = self.get_commits(&vcpkg_root_dir);
+            // versions of filesystem registry were located by path instead of git tree
+            if self.args.is_filesystem_registry(&name) {
+                continue;
+            }
 
-            let mut results = VcpkgGitTreeIndex::load(
-                &config::system_paths::DataPath::vcpkg_tree_index_json(
-                    self.args.index_directory.as_ref().unwrap(),
-                    &name,
-                ),
-                true,
-            )
-            .unwrap();
+            let index_dir = self.args.index_directory.clone().unwrap();
+            let mut results = VcpkgGitTreeIndex::load_index(&index_dir, &name)
+                .map(|index| index.as_ref().clone())
+                .unwrap_or_default();
 
-            let mut next_index = 0;
-            if let Some(index) = commits
-                .iter()
-                .position(|c| c.hash == results.check_point.hash)
-            {
-                next_index = index + 1;
+            let latest_commit = Self::get_latest_commit(&vcpkg_root_dir);
+            if latest_commit.hash == results.check_point.hash {
+                continue;
             }
 
-            let mut updated = false;
-            for (index, c) in commits[next_index..].iter().enumerate() {
-                let trees = self.get_git_trees(&vcpkg_root_dir, &c.hash, true);
-                for (git_tree, port_name) in &trees {
-                    if !results.index.contains_key(git_tree) {
-                        updated = true;
-                        results.index.insert(
-                            git_tree.clone(),
-                            VcpkgGitTreeInfo {
-                                port_name: port_name.clone(),
-                                commit_hash: c.hash.clone(),
-                                commit_date_time: c.date_time.clone(),
-                            },
-                        );
-                    }
+            // walk commits which touched ports/ only
+            let since = results.check_point.hash.clone();
+            let commits =
+                git::log::get_changed_trees(&vcpkg_root_dir, &since, VCPKG_PORTS_DIR_NAME);
+            tracing::info!(registry = name, commits = commits.len(), "index git trees");
+            for (c, trees) in &commits {
+                for (git_tree, port_name) in trees {
+                    Self::insert_git_tree(&mut results, git_tree, port_name, c);
                 }
+            }
 
-                if index % 200 == 0 || commits.len() < 1000 {
-                    if updated {
-                        updated = false;
-                        results.check_point = c.clone();
-                        results.dump(false, false);
+            // trees which were introduced by merge commits
+            let missing = Self::get_missing_git_trees(&vcpkg_root_dir, &results);
+            if !missing.is_empty() {
+                let merges = git::log::get_merge_commits(&vcpkg_root_dir, &since);
+                tracing::info!(
+                    registry = name,
+                    missing = missing.len(),
+                    merges = merges.len(),
+                    "index git trees of merge commits"
+                );
+                for (c, trees) in self.get_git_trees_parallel(&vcpkg_root_dir, &merges) {
+                    for (git_tree, port_name) in &trees {
+                        if missing.contains(git_tree) {
+                            Self::insert_git_tree(&mut results, git_tree, port_name, &c);
+                        }
                     }
-                    tracing::info!("[{index}] #{}# {:#?}", results.index.len(), c.date_time);
                 }
             }
 
-            if updated {
-                results.check_point = commits[commits.len() - 1].clone();
-                results.dump(false, false);
+            results.check_point = latest_commit;
+            results.dump_index(&index_dir, &name);
+            tracing::info!(
+                registry = name,
+                trees = results.index.len(),
+                "index git trees"
+            );
+        }
+    }
+
+    // keep the oldest commit which introduced the tree
+    fn insert_git_tree(
+        results: &mut VcpkgGitTreeIndex,
+        git_tree: &str,
+        port_name: &str,
+        commit: &GitCommitInfo,
+    ) {
+        if !results.index.contains_key(git_tree) {
+            results.index.insert(
+                git_tree.to_string(),
+                VcpkgGitTreeInfo {
+                    port_name: port_name.to_string(),
+                    commit_hash: commit.hash.clone(),
+                    commit_date_time: commit.date_time.clone(),
+                },
+            );
+        }
+    }
+
+    // git-tree entries of versions/*/*.json which were not indexed
+    fn get_missing_git_trees(vcpkg_root_dir: &str, results: &VcpkgGitTreeIndex) -> HashSet<String> {
+        let mut missing = HashSet::new();
+        for entry in walkdir::WalkDir::new(format!("{vcpkg_root_dir}/{VCPKG_VERSIONS_DIR_NAME}"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !file_name.ends_with(".json") || file_name == VCPKG_BASELINE_JSON_FILE_NAME {
+                continue;
+            }
+            if let Some(versions) =
+                VcpkgPortVersions::load(&entry.path().to_string_lossy().replace(r"\", "/"), true)
+            {
+                for v in versions.versions {
+                    if !results.index.contains_key(&v.git_tree) {
+                        missing.insert(v.git_tree);
+                    }
+                }
             }
         }
+        return missing;
+    }
+
+    // ls-tree commits on all cores, results keep the order of commits
+    fn get_git_trees_parallel(
+        &self,
+        vcpkg_root_dir: &str,
+        commits: &[GitCommitInfo],
+    ) -> Vec<(GitCommitInfo, Vec<(String, String)>)> {
+        if commits.is_empty() {
+            return vec![];
+        }
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+        let chunk_size = commits.len().div_ceil(threads);
+        return std::thread::scope(|scope| {
+            let handles = commits
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|c| (c.clone(), self.get_git_trees(vcpkg_root_dir, &c.hash, true)))
+                            .collect::<Vec<(GitCommitInfo, Vec<(String, String)>)>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap_or_default())
+                .collect()
+        });
     }
 
     fn get_git_trees(
@@ -217,8 +416,4 @@ impl VcpkgManager {
             git::log::GIT_LOG_FORMAT_COMMIT_HASH_DATE,
         );
     }
-
-    fn get_commits(&mut self, vcpkg_root_dir: &str) -> Vec<GitCommitInfo> {
-        return git::log::get_commits(vcpkg_root_dir, git::log::GIT_LOG_FORMAT_COMMIT_HASH_DATE);
-    }
 }
@@ -0,0 +1,185 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{errors::ErrorTag, util};
+
+// magic + format version (u32 le) + zstd compressed bincode
+static INDEX_FILE_MAGIC: &[u8; 4] = b"ASCX";
+static INDEX_FILE_FORMAT_VERSION: u32 = 1;
+static INDEX_FILE_HEADER_SIZE: usize = 8;
+static INDEX_FILE_ZSTD_LEVEL: i32 = 3;
+
+type LoadedIndexes = Mutex<HashMap<String, Arc<dyn Any + Send + Sync>>>;
+
+// indexes were read once per process and shared by all queries
+static LOADED_INDEXES: OnceLock<LoadedIndexes> = OnceLock::new();
+
+pub fn load<T>(path: &str) -> Option<Arc<T>>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    let loaded = LOADED_INDEXES.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(index) = loaded.lock().unwrap().get(path) {
+        if let Ok(index) = index.clone().downcast::<T>() {
+            return Some(index);
+        }
+    }
+
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.len() < INDEX_FILE_HEADER_SIZE || &bytes[..4] != INDEX_FILE_MAGIC {
+        tracing::error!(
+            func = "std::fs::read",
+            path = path,
+            error_tag = ErrorTag::ReadFileError.as_ref(),
+            message = "invalid index file, try to run asc vcpkg index"
+        );
+        return None;
+    }
+    let version = u32::from_le_bytes(bytes[4..INDEX_FILE_HEADER_SIZE].try_into().unwrap());
+    if version != INDEX_FILE_FORMAT_VERSION {
+        tracing::warn!(
+            path = path,
+            version = version,
+            message = "outdated index file, try to run asc vcpkg index"
+        );
+        return None;
+    }
+
+    let data = match zstd::stream::decode_all(&bytes[INDEX_FILE_HEADER_SIZE..]) {
+        Err(e) => {
+            tracing::error!(
+                func = "zstd::stream::decode_all",
+                path = path,
+                error_tag = ErrorTag::ReadFileError.as_ref(),
+                message = e.to_string()
+            );
+            return None;
+        }
+        Ok(data) => data,
+    };
+    let index = match bincode::deserialize::<T>(&data) {
+        Err(e) => {
+            tracing::error!(
+                func = "bincode::deserialize",
+                path = path,
+                error_tag = ErrorTag::ReadFileError.as_ref(),
+                message = e.to_string()
+            );
+            return None;
+        }
+        Ok(index) => Arc::new(index),
+    };
+
+    loaded
+        .lock()
+        .unwrap()
+        .insert(path.to_string(), index.clone());
+    return Some(index);
+}
+
+// binary index or the json index written by older versions, which was converted and removed
+pub fn load_or_migrate<T, F>(path: &str, json_path: &str, load_json: F) -> Option<Arc<T>>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+    F: Fn(&str) -> Option<T>,
+{
+    if util::fs::is_file_exists(path) {
+        return load(path);
+    }
+    if !util::fs::is_file_exists(json_path) {
+        return None;
+    }
+
+    tracing::warn!(from = json_path, to = path, message = "migrate index file");
+    let index = load_json(json_path)?;
+    if !dump(&index, path) {
+        return Some(Arc::new(index));
+    }
+    util::fs::remove_file(json_path);
+    return load(path);
+}
+
+pub fn dump<T>(index: &T, path: &str) -> bool
+where
+    T: Serialize,
+{
+    let data = match bincode::serialize(index) {
+        Err(e) => {
+            tracing::error!(
+                func = "bincode::serialize",
+                path = path,
+                error_tag = ErrorTag::WriteFileError.as_ref(),
+                message = e.to_string()
+            );
+            return false;
+        }
+        Ok(data) => data,
+    };
+    let compressed = match zstd::stream::encode_all(data.as_slice(), INDEX_FILE_ZSTD_LEVEL) {
+        Err(e) => {
+            tracing::error!(
+                func = "zstd::stream::encode_all",
+                path = path,
+                error_tag = ErrorTag::WriteFileError.as_ref(),
+                message = e.to_string()
+            );
+            return false;
+        }
+        Ok(compressed) => compressed,
+    };
+
+    let mut bytes = Vec::with_capacity(INDEX_FILE_HEADER_SIZE + compressed.len());
+    bytes.extend_from_slice(INDEX_FILE_MAGIC);
+    bytes.extend_from_slice(&INDEX_FILE_FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&compressed);
+
+    // write then rename, readers never see a partial index
+    let temp_path = format!("{path}.tmp");
+    if let Err(e) =
+        std::fs::write(&temp_path, &bytes).and_then(|_| std::fs::rename(&temp_path, path))
+    {
+        tracing::error!(
+            func = "std::fs::write",
+            path = path,
+            error_tag = ErrorTag::WriteFileError.as_ref(),
+            message = e.to_string()
+        );
+        return false;
+    }
+
+    if let Some(loaded) = LOADED_INDEXES.get() {
+        loaded.lock().unwrap().remove(path);
+    }
+    return true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_load() {
+        let path = std::env::temp_dir()
+            .join(format!("asc_index_file_{}.bin", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let index = HashMap::from([(String::from("fmt"), String::from("11.0.2"))]);
+        assert!(dump(&index, &path));
+        assert_eq!(
+            load::<HashMap<String, String>>(&path).unwrap().as_ref(),
+            &index
+        );
+
+        std::fs::remove_file(&path).unwrap();
+
+        let json_path = format!("{path}.json");
+        std::fs::write(&json_path, b"{}").unwrap();
+        assert!(load::<HashMap<String, String>>(&json_path).is_none());
+        std::fs::remove_file(&json_path).unwrap();
+    }
+}
@@ -1,12 +1,14 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use super::search::get_port_version_commit_info;
+use super::search::{get_baseline, get_port_version_commit_info};
 
 use crate::{
     cli::commands::VcpkgArgs,
     config::{
         self,
-        project::{DependencyConfig, PackageConfig},
+        project::{
+            DependencyConfig, LockConfig, LockedPackage, LockedRegistry, PackageConfig, VcpkgConfig,
+        },
         relative_paths::{self, ASC_TOML_FILE_NAME, VCPKG_JSON_FILE_NAME},
         vcpkg::{
             port::{VcpkgDependency, VcpkgDependencyDesc, VcpkgJsonDependency, VcpkgPortJson},
@@ -15,6 +17,7 @@ use crate::{
             versions_port::{VcpkgPortTreeVersion, VcpkgPortVersions},
         },
     },
+    errors::ErrorTag,
     git::{self, log::GitCommitInfo},
     util,
 };
@@ -24,17 +27,36 @@ static VCPKG_PORT_VERSION_KEY: &str = "version";
 static VCPKG_PORT_PLATFORM_KEY: &str = "platform";
 static VCPKG_FEATURE_PLATFORM_DELIMITER: &str = "@";
 static VCPKG_REGISTRY_KIND_GIT: &str = "git";
+static VCPKG_REGISTRY_KIND_FILESYSTEM: &str = "filesystem";
+static VCPKG_FILESYSTEM_BASELINE: &str = "default";
 
-pub fn gen_vcpkg_configurations(dependencies: &BTreeMap<String, DependencyConfig>) {
-    let mut grouped_names = BTreeMap::new();
-    // ascending date time commits group by registry
-    let mut grouped_commits = BTreeMap::new();
+pub fn gen_vcpkg_configurations(
+    dependencies: &BTreeMap<String, DependencyConfig>,
+    vcpkg_conf: &VcpkgConfig,
+    lock_path: &str,
+) -> bool {
+    // use locked versions and baselines verbatim unless dependencies were changed
+    let mut lock_data = LockConfig::load(lock_path, true).unwrap();
+    if lock_data.is_up_to_date(dependencies) {
+        tracing::info!("use locked versions in {}", lock_path);
+    } else {
+        lock_data = match resolve_lock(dependencies, lock_path) {
+            None => return false,
+            Some(lock_data) => lock_data,
+        };
+        lock_data.dump(true, false);
+    }
 
     let mut vcpkg_data = VcpkgDependency::load(relative_paths::VCPKG_JSON_FILE_NAME, true).unwrap();
     vcpkg_data.dependencies.clear();
     vcpkg_data.overrides.clear();
 
     for (port_name, desc) in dependencies {
+        // git, path dependencies were built from source
+        if desc.is_source() {
+            continue;
+        }
+
         let mut dep = VcpkgDependencyDesc::default();
         dep.name = port_name.clone();
         if !desc.features.is_empty() {
@@ -56,78 +78,26 @@ pub fn gen_vcpkg_configurations(dependencies: &BTreeMap<String, DependencyConfig
                 }
             };
         }
+        // vcpkg overrides accept exact versions only
+        let version = match lock_data.packages.get(port_name) {
+            None => {
+                tracing::error!(
+                    func = "lock_data.packages.get",
+                    port = port_name,
+                    version = desc.version,
+                    error_tag = ErrorTag::VcpkgPortVersionNotFound.as_ref(),
+                    message = "try to run asc vcpkg update, asc vcpkg index"
+                );
+                return false;
+            }
+            Some(locked) => locked.format_version_text(),
+        };
         vcpkg_data.dependencies.push(dep);
         vcpkg_data.overrides.push(BTreeMap::from([
             (String::from(VCPKG_PORT_NAME_KEY), port_name.clone()),
-            (String::from(VCPKG_PORT_VERSION_KEY), desc.version.clone()),
+            (String::from(VCPKG_PORT_VERSION_KEY), version),
         ]));
-
-        if let Some((registry, commit)) = get_port_version_commit_info(port_name, &desc.version) {
-            grouped_names
-                .entry(registry.clone())
-                .or_insert_with(BTreeSet::new)
-                .insert(port_name.clone());
-            grouped_commits
-                .entry(registry)
-                .or_insert_with(BTreeSet::new)
-                .insert((
-                    commit.date_time,
-                    commit.hash,
-                    port_name,
-                    desc.version.clone(),
-                ));
-        }
-    }
-
-    tracing::info!("baselines: {:#?}", grouped_commits);
-
-    let vcpkg_args = VcpkgArgs::load_or_default();
-    let cwd = util::fs::get_cwd();
-    let mut registry_baseline = BTreeMap::new();
-    for (registry, commits) in &grouped_commits {
-        for (date_time, hash, _, _) in commits {
-            let (_registry, url, branch, vcpkg_root_dir) = vcpkg_args.get_registry(&registry);
-            util::fs::set_cwd(&vcpkg_root_dir);
-
-            let stdout = git::show::run(&vcpkg_root_dir, &hash);
-
-            if let Some(baseline_data) = VcpkgBaseline::loads(&stdout, false) {
-                // overwrite versions
-                for desc in vcpkg_data.overrides.iter_mut() {
-                    if let Some(v) = baseline_data
-                        .default
-                        .get(desc.get(VCPKG_PORT_NAME_KEY).unwrap())
-                    {
-                        desc.insert(
-                            String::from(VCPKG_PORT_VERSION_KEY),
-                            v.format_version_text(),
-                        );
-                    }
-                }
-
-                // search baseline
-                let mut found = true;
-                let mut versions = vec![];
-                for name in grouped_names.get(registry).unwrap_or(&BTreeSet::new()) {
-                    if let Some(version) = baseline_data.default.get(name) {
-                        versions.push(format!("{name}={}", version.format_version_text()));
-                    } else {
-                        found = false;
-                        tracing::warn!("can't found {} in {hash} @ {date_time}", name);
-                        break;
-                    }
-                }
-                if found {
-                    tracing::info!(
-                        "set baseline to {hash} @ {date_time} ({})",
-                        versions.join(", ")
-                    );
-                    registry_baseline.insert(registry, (url, branch, hash));
-                }
-            }
-        }
     }
-    util::fs::set_cwd(&cwd);
 
     if vcpkg_data.dependencies.is_empty() {
         tracing::error!("can't found any dependencies in {}", ASC_TOML_FILE_NAME);
@@ -139,7 +109,7 @@ pub fn gen_vcpkg_configurations(dependencies: &BTreeMap<String, DependencyConfig
         super::bootstrap::run();
     }
 
-    if registry_baseline.is_empty() {
+    if lock_data.registries.is_empty() {
         if !vcpkg_data.dependencies.is_empty() {
             tracing::error!("can't found all dependencies in same baseline");
         }
@@ -148,32 +118,196 @@ pub fn gen_vcpkg_configurations(dependencies: &BTreeMap<String, DependencyConfig
             VcpkgConfiguration::load(relative_paths::VCPKG_CONFIGURATION_JSON_FILE_NAME, true)
                 .unwrap();
         vcpkg_conf_data.registries.clear();
-        for (registry, (url, branch, hash)) in registry_baseline {
+        let vcpkg_args = VcpkgArgs::load_or_default();
+        for (registry, locked) in &lock_data.registries {
+            // filesystem registry was referenced by local path
+            let (kind, repository, path) = if VcpkgArgs::is_filesystem_url(&locked.repository) {
+                (
+                    VCPKG_REGISTRY_KIND_FILESYSTEM,
+                    String::new(),
+                    vcpkg_args.get_registry(registry).3,
+                )
+            } else {
+                (
+                    VCPKG_REGISTRY_KIND_GIT,
+                    locked.repository.clone(),
+                    String::new(),
+                )
+            };
             if registry == config::relative_paths::VCPKG_DIR_NAME {
                 vcpkg_conf_data.default_registry = VcpkgDefaultRegistry {
-                    kind: String::from(VCPKG_REGISTRY_KIND_GIT),
-                    repository: url,
-                    baseline: hash.clone(),
+                    kind: String::from(kind),
+                    repository: repository,
+                    baseline: locked.baseline.clone(),
+                    path: path,
                 };
             } else {
                 vcpkg_conf_data.registries.push(VcpkgRegistry {
-                    kind: String::from(VCPKG_REGISTRY_KIND_GIT),
-                    reference: branch,
-                    repository: url,
-                    baseline: hash.clone(),
-                    packages: grouped_names
-                        .get(registry)
-                        .unwrap()
+                    kind: String::from(kind),
+                    reference: if path.is_empty() {
+                        locked.branch.clone()
+                    } else {
+                        String::new()
+                    },
+                    repository: repository,
+                    path: path,
+                    baseline: locked.baseline.clone(),
+                    packages: lock_data
+                        .packages
                         .iter()
-                        .map(|s| s.clone())
+                        .filter(|(name, package)| {
+                            &package.registry == registry && dependencies.contains_key(*name)
+                        })
+                        .map(|(name, _package)| name.clone())
                         .collect::<Vec<String>>(),
                     ..Default::default()
                 });
             }
         }
+        vcpkg_conf_data.overlay_ports = vcpkg_conf.overlay_ports.clone();
+        vcpkg_conf_data.overlay_triplets = vcpkg_conf.overlay_triplets.clone();
         // write vcpkg-configuration.json
         vcpkg_conf_data.dump(true, false);
     }
+
+    return true;
+}
+
+// re-resolve baselines and write asc.lock
+pub fn gen_lock(dependencies: &BTreeMap<String, DependencyConfig>, lock_path: &str) -> bool {
+    return match resolve_lock(dependencies, lock_path) {
+        None => false,
+        Some(lock_data) => lock_data.dump(true, false),
+    };
+}
+
+// ports without any version satisfying their requirements fail the lock
+pub fn resolve_lock(
+    dependencies: &BTreeMap<String, DependencyConfig>,
+    lock_path: &str,
+) -> Option<LockConfig> {
+    // ascending date time commits group by registry
+    let mut grouped_commits = BTreeMap::new();
+
+    for (port_name, desc) in dependencies {
+        if desc.is_source() {
+            continue;
+        }
+
+        match get_port_version_commit_info(&desc.registry, port_name, &desc.version) {
+            None => {
+                tracing::error!(
+                    func = "get_port_version_commit_info",
+                    port = port_name,
+                    version = desc.version,
+                    error_tag = ErrorTag::VcpkgPortVersionNotFound.as_ref(),
+                    message = "try to run asc vcpkg update, asc vcpkg index"
+                );
+                return None;
+            }
+            Some((registry, version, commit)) => {
+                tracing::info!("resolve {port_name} {} to {version}", desc.version);
+                grouped_commits
+                    .entry(registry)
+                    .or_insert_with(BTreeSet::new)
+                    .insert((commit.date_time, commit.hash, port_name.clone(), version));
+            }
+        }
+    }
+
+    tracing::info!("baselines: {:#?}", grouped_commits);
+
+    let mut lock_data = LockConfig {
+        path: lock_path.to_string(),
+        ..Default::default()
+    };
+
+    let vcpkg_args = VcpkgArgs::load_or_default();
+    let cwd = util::fs::get_cwd();
+    for (registry, commits) in &grouped_commits {
+        let (_registry, url, branch, vcpkg_root_dir) = vcpkg_args.get_registry(&registry);
+        util::fs::set_cwd(&vcpkg_root_dir);
+
+        // the newest commit of which baseline contains all ports of the registry
+        for (date_time, hash, _, _) in commits.iter().rev() {
+            // filesystem registry has only one baseline
+            let hash = if VcpkgArgs::is_filesystem_url(&url) {
+                VCPKG_FILESYSTEM_BASELINE.to_string()
+            } else {
+                hash.clone()
+            };
+
+            if let Some(baseline_data) = get_baseline(&vcpkg_args, registry, &hash) {
+                match lock_registry(
+                    registry,
+                    &url,
+                    &branch,
+                    &hash,
+                    &baseline_data,
+                    commits,
+                    dependencies,
+                ) {
+                    None => {
+                        tracing::warn!(
+                            "can't found all ports of {registry} in {hash} @ {date_time}"
+                        );
+                    }
+                    Some((locked_registry, packages)) => {
+                        tracing::info!("set baseline of {registry} to {hash} @ {date_time}");
+                        lock_data
+                            .registries
+                            .insert(registry.clone(), locked_registry);
+                        lock_data.packages.extend(packages);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    util::fs::set_cwd(&cwd);
+
+    return Some(lock_data);
+}
+
+// ports keep versions selected by their requirements, baseline was only used as registry baseline
+fn lock_registry(
+    registry: &str,
+    url: &str,
+    branch: &str,
+    hash: &str,
+    baseline_data: &VcpkgBaseline,
+    selected: &BTreeSet<(String, String, String, String)>,
+    dependencies: &BTreeMap<String, DependencyConfig>,
+) -> Option<(LockedRegistry, BTreeMap<String, LockedPackage>)> {
+    let mut packages = BTreeMap::new();
+    for (_date_time, _hash, name, version_text) in selected {
+        if !baseline_data.default.contains_key(name) {
+            return None;
+        }
+        let (version, port_version) = match version_text.split_once('#') {
+            None => (version_text.clone(), 0),
+            Some((v, p)) => (v.to_string(), p.parse::<u32>().unwrap_or_default()),
+        };
+        packages.insert(
+            name.clone(),
+            LockedPackage {
+                requirement: dependencies[name].version.clone(),
+                version: version,
+                port_version: port_version,
+                registry: registry.to_string(),
+                baseline: hash.to_string(),
+                features: dependencies[name].features.clone(),
+            },
+        );
+    }
+    return Some((
+        LockedRegistry {
+            repository: url.to_string(),
+            branch: branch.to_string(),
+            baseline: hash.to_string(),
+        },
+        packages,
+    ));
 }
 
 pub fn gen_port_json(
@@ -332,6 +466,7 @@ pub fn gen_port_versions(
     repo_root_dir: &String,
     package_conf: &PackageConfig,
     port_version: u32,
+    filesystem: bool,
 ) -> bool {
     let mut versions_data = VcpkgPortVersions::load(
         &config::system_paths::DataPath::vcpkg_versions_port_json_path(
@@ -355,15 +490,20 @@ pub fn gen_port_versions(
         }
     }
 
-    versions_data.versions.insert(
-        0,
-        VcpkgPortTreeVersion {
-            git_tree: git::rev_parse::run("HEAD", &package_conf.name, repo_root_dir),
-            version: Some(package_conf.version.clone()),
-            port_version: port_version,
-            ..Default::default()
-        },
-    );
+    let mut version = VcpkgPortTreeVersion {
+        version: Some(package_conf.version.clone()),
+        port_version: port_version,
+        ..Default::default()
+    };
+    if filesystem {
+        match snapshot_port(repo_root_dir, package_conf, port_version) {
+            None => return false,
+            Some(path) => version.path = path,
+        }
+    } else {
+        version.git_tree = git::rev_parse::run("HEAD", &package_conf.name, repo_root_dir);
+    }
+    versions_data.versions.insert(0, version);
 
     let mut result = versions_data.dump(true, false);
 
@@ -395,3 +535,125 @@ pub fn gen_port_versions(
 
     return result;
 }
+
+// copy ports/<port> to snapshots/<port>/<version>_<port version>, returns $/ relative path
+fn snapshot_port(
+    repo_root_dir: &str,
+    package_conf: &PackageConfig,
+    port_version: u32,
+) -> Option<String> {
+    let port_dir =
+        config::system_paths::DataPath::vcpkg_ports_dir_path(repo_root_dir, &package_conf.name);
+    let snapshot_dir = config::system_paths::DataPath::vcpkg_port_snapshot_dir_path(
+        repo_root_dir,
+        &package_conf.name,
+        &package_conf.version,
+        port_version,
+    );
+
+    if !util::fs::copy_dir(&port_dir, &snapshot_dir) {
+        return None;
+    }
+
+    return Some(format!(
+        "$/{}",
+        snapshot_dir
+            .strip_prefix(repo_root_dir)
+            .unwrap_or(&snapshot_dir)
+            .trim_start_matches('/')
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_lock_unresolved() {
+        // requirement which can't select any version fails instead of being dropped
+        let dependencies = BTreeMap::from([(
+            String::from("fmt"),
+            DependencyConfig {
+                version: String::from("not a version"),
+                ..Default::default()
+            },
+        )]);
+        assert!(resolve_lock(&dependencies, "asc.lock").is_none());
+    }
+
+    #[test]
+    fn test_lock_registry() {
+        let baseline_data = VcpkgBaseline::loads(
+            r#"{"default": {"fmt": {"baseline": "11.0.2", "port-version": 1}, "spdlog": {"baseline": "1.14.1", "port-version": 0}}}"#,
+            false,
+        )
+        .unwrap();
+        let dependencies = BTreeMap::from([
+            (
+                String::from("fmt"),
+                DependencyConfig {
+                    version: String::from("^10"),
+                    ..Default::default()
+                },
+            ),
+            (
+                String::from("spdlog"),
+                DependencyConfig {
+                    version: String::from("=1.14.1"),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        // fmt and spdlog were resolved to different commits
+        let selected = BTreeSet::from([
+            (
+                String::from("2024-01-01 00:00:00 +0800"),
+                String::from("aaaa"),
+                String::from("fmt"),
+                String::from("10.2.1#2"),
+            ),
+            (
+                String::from("2024-06-01 00:00:00 +0800"),
+                String::from("bbbb"),
+                String::from("spdlog"),
+                String::from("1.14.1"),
+            ),
+        ]);
+
+        let (registry, packages) = lock_registry(
+            "vcpkg",
+            "https://github.com/microsoft/vcpkg.git",
+            "master",
+            "bbbb",
+            &baseline_data,
+            &selected,
+            &dependencies,
+        )
+        .unwrap();
+        assert_eq!(registry.baseline, "bbbb");
+        // selected version instead of the baseline version
+        assert_eq!(packages["fmt"].version, "10.2.1");
+        assert_eq!(packages["fmt"].port_version, 2);
+        assert_eq!(packages["fmt"].format_version_text(), "10.2.1#2");
+        assert_eq!(packages["fmt"].requirement, "^10");
+        assert_eq!(packages["spdlog"].format_version_text(), "1.14.1");
+        assert_eq!(packages["spdlog"].baseline, "bbbb");
+
+        // port was not in the baseline
+        let baseline_data = VcpkgBaseline::loads(
+            r#"{"default": {"fmt": {"baseline": "11.0.2", "port-version": 1}}}"#,
+            false,
+        )
+        .unwrap();
+        assert!(lock_registry(
+            "vcpkg",
+            "https://github.com/microsoft/vcpkg.git",
+            "master",
+            "aaaa",
+            &baseline_data,
+            &selected,
+            &dependencies,
+        )
+        .is_none());
+    }
+}
@@ -1,11 +1,16 @@
 pub mod bootstrap;
+pub mod cache;
 pub mod cmake;
 pub mod config;
+pub mod doctor;
 pub mod flatten;
 pub mod index;
+pub mod index_file;
 pub mod json;
+pub mod overlay;
 pub mod search;
 pub mod update;
+pub mod version;
 
 use std::collections::{BTreeMap, HashMap};
 
@@ -76,24 +81,28 @@ impl VcpkgArgs {
 
     pub fn update(&mut self, other: &Self, force: bool, dump: bool) -> bool {
         let mut registries = BTreeMap::new();
-        for (registry, url, branch, vcpkg_root_dir) in self.flatten_registry() {
-            registries.insert(registry, (url, branch, vcpkg_root_dir));
+        for (registry, url, branch, vcpkg_root_dir, priority, packages) in self.parse_registry() {
+            registries.insert(registry, (url, branch, vcpkg_root_dir, priority, packages));
         }
-        for (registry, url, branch, vcpkg_root_dir) in other.flatten_registry() {
+        for (registry, url, branch, vcpkg_root_dir, priority, packages) in other.parse_registry() {
             if force {
-                registries.insert(registry, (url, branch, vcpkg_root_dir));
+                registries.insert(registry, (url, branch, vcpkg_root_dir, priority, packages));
             } else {
                 if !registries.contains_key(&registry) {
-                    registries.insert(registry, (url, branch, vcpkg_root_dir));
+                    registries.insert(registry, (url, branch, vcpkg_root_dir, priority, packages));
                 }
             }
         }
         self.registry.clear();
-        for (_registry, (url, branch, vcpkg_root_dir)) in registries {
-            self.registry.push(format!(
-                "{}?branch={}&directory={}",
-                url, branch, vcpkg_root_dir
-            ));
+        for (_registry, (url, branch, vcpkg_root_dir, priority, packages)) in registries {
+            let mut spec = format!("{}?branch={}&directory={}", url, branch, vcpkg_root_dir);
+            if priority != 0 {
+                spec.push_str(&format!("&priority={priority}"));
+            }
+            if !packages.is_empty() {
+                spec.push_str(&format!("&packages={}", packages.join(",")));
+            }
+            self.registry.push(spec);
         }
 
         let (mut remove_dirs_count, all_dirs_count) = (0, 3);
@@ -124,6 +133,14 @@ impl VcpkgArgs {
         if remove_dirs_count == all_dirs_count {
             util::fs::remove_dir(&system_paths::DataPath::prefix());
         }
+        if force || self.env_binary_sources.is_none() {
+            if let Some(env_binary_sources) = &other.env_binary_sources {
+                self.env_binary_sources = Some(env_binary_sources.clone());
+                for dir in cache::files_provider_dirs(env_binary_sources) {
+                    util::fs::create_dirs(&dir);
+                }
+            }
+        }
 
         if dump {
             return self.dump(true, false);
@@ -0,0 +1,160 @@
+use crate::{
+    config::{
+        self,
+        project::{LockConfig, ProjectConfig},
+        relative_paths::{ASC_LOCK_FILE_NAME, ASC_OVERLAY_PORTS_DIR_NAME},
+        vcpkg::versions_port::VcpkgPortVersions,
+    },
+    errors::ErrorTag,
+    git, util,
+};
+
+use super::{search, VcpkgManager};
+
+static OVERLAY_TAR_FILE_NAME: &str = "port.tar";
+
+impl VcpkgManager {
+    // copy port at locked, required or latest version into overlay_ports/<port> for editing
+    pub fn overlay(&mut self) -> bool {
+        let port_name = match self.args.args.first() {
+            None => {
+                tracing::error!(
+                    call = "self.args.args.first",
+                    error_tag = ErrorTag::InvalidCliArgsError.as_ref(),
+                    message = "asc vcpkg overlay <port> [version]"
+                );
+                return false;
+            }
+            Some(port_name) => port_name.clone(),
+        };
+
+        let mut project_conf = match ProjectConfig::read_project_conf() {
+            None => return false,
+            Some(conf) => conf,
+        };
+
+        let target_dir = format!("{ASC_OVERLAY_PORTS_DIR_NAME}/{port_name}");
+        if util::fs::is_dir_exists(&target_dir) {
+            tracing::error!(
+                path = target_dir,
+                error_tag = ErrorTag::DirectoryExistsError.as_ref(),
+            );
+            return false;
+        }
+
+        let registry = project_conf
+            .dependencies
+            .get(&port_name)
+            .map(|dep| dep.registry.clone())
+            .unwrap_or_default();
+        let locked = LockConfig::load(ASC_LOCK_FILE_NAME, true)
+            .and_then(|lock| lock.packages.get(&port_name).cloned());
+        let requirement = match (self.args.args.get(1), &locked) {
+            (Some(requirement), _) => requirement.clone(),
+            (None, Some(locked)) => format!("={}", locked.format_version_text()),
+            (None, None) => String::from("*"),
+        };
+        let registry = locked.map(|l| l.registry).unwrap_or(registry);
+
+        let (registry, version, _commit) =
+            match search::get_port_version_commit_info(&registry, &port_name, &requirement) {
+                None => {
+                    tracing::error!(
+                        call = "search::get_port_version_commit_info",
+                        port = port_name,
+                        version = requirement,
+                        error_tag = ErrorTag::VcpkgPortVersionNotFound.as_ref(),
+                        message = "try to run asc vcpkg update, asc vcpkg index"
+                    );
+                    return false;
+                }
+                Some(info) => info,
+            };
+
+        let (_registry, _url, _branch, vcpkg_root_dir) = self.args.get_registry(&registry);
+        let port_version = VcpkgPortVersions::load(
+            &config::system_paths::DataPath::vcpkg_versions_port_json_path(
+                &vcpkg_root_dir,
+                &port_name,
+            ),
+            false,
+        )
+        .and_then(|versions| {
+            versions
+                .versions
+                .into_iter()
+                .find(|v| v.format_version_text() == version)
+        });
+        let port_version = match port_version {
+            None => {
+                tracing::error!(
+                    port = port_name,
+                    version = version,
+                    error_tag = ErrorTag::VcpkgPortVersionNotFound.as_ref(),
+                );
+                return false;
+            }
+            Some(v) => v,
+        };
+
+        util::fs::create_dirs(&target_dir);
+        let copied = if port_version.path.is_empty() {
+            Self::extract_git_tree(&vcpkg_root_dir, &port_version.git_tree, &target_dir)
+        } else {
+            util::fs::copy_dir(
+                &search::get_port_version_dir(&vcpkg_root_dir, &port_version.path),
+                &target_dir,
+            )
+        };
+        if !copied {
+            util::fs::remove_dirs(&target_dir);
+            return false;
+        }
+        tracing::info!(
+            port = port_name,
+            version = version,
+            registry = registry,
+            path = target_dir,
+            message = "overlay"
+        );
+
+        let overlay_ports_dir = ASC_OVERLAY_PORTS_DIR_NAME.to_string();
+        if !project_conf
+            .vcpkg
+            .overlay_ports
+            .contains(&overlay_ports_dir)
+        {
+            project_conf.vcpkg.overlay_ports.push(overlay_ports_dir);
+            return project_conf.write_project_conf();
+        }
+        return true;
+    }
+
+    fn extract_git_tree(vcpkg_root_dir: &str, git_tree: &str, target_dir: &str) -> bool {
+        let tar_path = format!(
+            "{}/{target_dir}/{OVERLAY_TAR_FILE_NAME}",
+            util::fs::get_cwd()
+        );
+        git::archive::run(vcpkg_root_dir, "tar", &tar_path, git_tree, "");
+        if !util::fs::is_file_exists(&tar_path) {
+            tracing::error!(
+                call = "git::archive::run",
+                git_tree = git_tree,
+                error_tag = ErrorTag::FileNotFoundError.as_ref(),
+            );
+            return false;
+        }
+        let result = util::shell::run(
+            "tar",
+            &vec!["-xf", OVERLAY_TAR_FILE_NAME],
+            target_dir,
+            false,
+            false,
+            true,
+        )
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+        util::fs::remove_file(&tar_path);
+        return result;
+    }
+}
@@ -1,33 +1,241 @@
-use super::{index::VcpkgSearchIndex, VcpkgManager};
+use std::{collections::BTreeMap, path::Path};
+
+use serde::Serialize;
+
+use super::{
+    index::{VcpkgPortSummary, VcpkgSearchIndex},
+    version::{self, VersionReq},
+    VcpkgManager,
+};
 
 use crate::{
     cli::commands::VcpkgArgs,
-    config::{self, vcpkg::versions_baseline::VcpkgPortVersion},
-    git::log::GitCommitInfo,
+    config::{
+        self,
+        relative_paths::{VCPKG_CONTROL_FILE_NAME, VCPKG_JSON_FILE_NAME},
+        vcpkg::{
+            port_manifest::VcpkgPortManifest,
+            versions_baseline::{VcpkgBaseline, VcpkgPortVersion},
+            versions_port::VcpkgPortVersions,
+        },
+    },
+    errors::ErrorTag,
+    git::{self, log::GitCommitInfo},
     util,
 };
 
+// highest version matches requirement in the registry resolved for the port
 pub fn get_port_version_commit_info(
+    registry: &str,
     port_name: &str,
-    version: &str,
-) -> Option<(String, GitCommitInfo)> {
+    requirement: &str,
+) -> Option<(String, String, GitCommitInfo)> {
+    let requirement = VersionReq::parse(requirement)?;
     let vcpkg_manager = VcpkgManager::new(VcpkgArgs::load_or_default());
 
-    for (registry, _url, _branch, vcpkg_root_dir) in vcpkg_manager.args.flatten_registry() {
-        for (v, c, d) in vcpkg_manager.get_port_versions(&vcpkg_root_dir, &registry, port_name) {
-            if v == version {
-                return Some((
-                    registry,
-                    GitCommitInfo {
-                        hash: c,
-                        date_time: d,
-                        path: String::new(),
-                    },
-                ));
+    let (registry, vcpkg_root_dir) = resolve_registry(&vcpkg_manager.args, registry, port_name)?;
+    let versions = vcpkg_manager.get_port_versions(&vcpkg_root_dir, &registry, port_name);
+    let texts = versions
+        .iter()
+        .map(|(v, _c, _d)| v.clone())
+        .collect::<Vec<String>>();
+    let selected = version::select(&requirement, &texts)?;
+    let (v, c, d) = versions.into_iter().find(|(v, _c, _d)| v == selected)?;
+    return Some((
+        registry,
+        v,
+        GitCommitInfo {
+            hash: c,
+            date_time: d,
+            path: String::new(),
+        },
+    ));
+}
+
+// versions of port in the registry resolved for it, descending
+pub fn get_port_versions(registry: &str, port_name: &str) -> Vec<String> {
+    let vcpkg_manager = VcpkgManager::new(VcpkgArgs::load_or_default());
+
+    match resolve_registry(&vcpkg_manager.args, registry, port_name) {
+        None => return vec![],
+        Some((registry, vcpkg_root_dir)) => {
+            return vcpkg_manager
+                .get_port_versions(&vcpkg_root_dir, &registry, port_name)
+                .into_iter()
+                .map(|(v, _c, _d)| v)
+                .collect();
+        }
+    }
+}
+
+// (registry, vcpkg root dir) of port, explicit registry or the one selected by select_registries
+pub fn resolve_registry(
+    vcpkg_args: &VcpkgArgs,
+    registry: &str,
+    port_name: &str,
+) -> Option<(String, String)> {
+    if !registry.is_empty() {
+        let (name, _url, _branch, vcpkg_root_dir) = vcpkg_args.get_registry(registry);
+        if name.is_empty() {
+            return None;
+        }
+        return Some((name, vcpkg_root_dir));
+    }
+
+    let roots = vcpkg_args
+        .flatten_registry()
+        .into_iter()
+        .map(|(name, _url, _branch, vcpkg_root_dir)| (name, vcpkg_root_dir))
+        .collect::<BTreeMap<String, String>>();
+    let candidates = vcpkg_args
+        .flatten_registry_scopes()
+        .into_iter()
+        .filter(|(name, _priority, _packages)| {
+            Path::new(
+                &config::system_paths::DataPath::vcpkg_versions_port_json_path(
+                    &roots[name],
+                    port_name,
+                ),
+            )
+            .exists()
+        })
+        .collect::<Vec<(String, i64, Vec<String>)>>();
+
+    let selected = select_registries(port_name, &candidates);
+    if selected.len() > 1 {
+        tracing::error!(
+            port = port_name,
+            registries = selected.join(", "),
+            error_tag = ErrorTag::VcpkgPortAmbiguous.as_ref(),
+            message = "set priority or packages of registries, or --registry"
+        );
+        return None;
+    }
+    let name = selected.into_iter().next()?;
+    let vcpkg_root_dir = roots[&name].clone();
+    return Some((name, vcpkg_root_dir));
+}
+
+// registries which contain the port were selected by
+// 1. the most specific packages pattern (exact name, then longest prefix of xxx-*), then highest priority
+// 2. highest priority of registries without packages patterns
+// more than one result means the port was ambiguous
+fn select_registries(port_name: &str, candidates: &[(String, i64, Vec<String>)]) -> Vec<String> {
+    let mut scoped = vec![];
+    let mut unscoped = vec![];
+    for (name, priority, packages) in candidates {
+        if packages.is_empty() {
+            unscoped.push((name.clone(), 0, *priority));
+        } else if let Some(specificity) = packages
+            .iter()
+            .filter_map(|pattern| match_package_pattern(pattern, port_name))
+            .max()
+        {
+            scoped.push((name.clone(), specificity, *priority));
+        }
+    }
+
+    let best = if scoped.is_empty() { unscoped } else { scoped };
+    let top = match best.iter().map(|(_n, s, p)| (*s, *p)).max() {
+        None => return vec![],
+        Some(top) => top,
+    };
+    return best
+        .into_iter()
+        .filter(|(_n, s, p)| (*s, *p) == top)
+        .map(|(n, _s, _p)| n)
+        .collect();
+}
+
+// exact name beats any prefix, longer prefix beats shorter one
+fn match_package_pattern(pattern: &str, port_name: &str) -> Option<usize> {
+    match pattern.strip_suffix('*') {
+        None => {
+            if pattern == port_name {
+                return Some(usize::MAX);
+            }
+        }
+        Some(prefix) => {
+            if port_name.starts_with(prefix) {
+                return Some(prefix.len());
             }
         }
     }
-    None
+    return None;
+}
+
+// vcpkg.json or CONTROL of port at version from registry git history
+pub fn get_port_manifest(
+    registry: &str,
+    port_name: &str,
+    version: &str,
+) -> Option<VcpkgPortManifest> {
+    if let Some(text) = get_port_file(registry, port_name, version, VCPKG_JSON_FILE_NAME) {
+        return VcpkgPortManifest::loads(&text, false);
+    }
+    if let Some(text) = get_port_file(registry, port_name, version, VCPKG_CONTROL_FILE_NAME) {
+        return VcpkgPortManifest::from_control_text(&text);
+    }
+    return None;
+}
+
+// file in ports/<port> at version from registry git history or filesystem registry
+pub fn get_port_file(
+    registry: &str,
+    port_name: &str,
+    version: &str,
+    file_name: &str,
+) -> Option<String> {
+    let vcpkg_args = VcpkgArgs::load_or_default();
+    let (_registry, _url, _branch, vcpkg_root_dir) = vcpkg_args.get_registry(registry);
+    if vcpkg_root_dir.is_empty() {
+        return None;
+    }
+
+    let versions = VcpkgPortVersions::load(
+        &config::system_paths::DataPath::vcpkg_versions_port_json_path(&vcpkg_root_dir, port_name),
+        true,
+    )?;
+    let found = versions
+        .versions
+        .iter()
+        .find(|v| v.format_version_text() == version)?;
+    if !found.path.is_empty() {
+        return std::fs::read_to_string(format!(
+            "{}/{file_name}",
+            get_port_version_dir(&vcpkg_root_dir, &found.path)
+        ))
+        .ok();
+    }
+
+    return git::show::file(&vcpkg_root_dir, &found.git_tree, file_name);
+}
+
+// $/ports/fmt/10.0.0 -> <registry dir>/ports/fmt/10.0.0
+pub fn get_port_version_dir(vcpkg_root_dir: &str, path: &str) -> String {
+    return format!(
+        "{vcpkg_root_dir}/{}",
+        path.trim_start_matches('$').trim_start_matches('/')
+    );
+}
+
+// versions/baseline.json at commit of git registry, or in the directory of filesystem registry
+pub fn get_baseline(
+    vcpkg_args: &VcpkgArgs,
+    registry: &str,
+    commit_hash: &str,
+) -> Option<VcpkgBaseline> {
+    let (_registry, url, _branch, vcpkg_root_dir) = vcpkg_args.get_registry(registry);
+    if vcpkg_root_dir.is_empty() {
+        return None;
+    }
+    if VcpkgArgs::is_filesystem_url(&url) {
+        return VcpkgBaseline::load(
+            &config::system_paths::DataPath::vcpkg_versions_baseline_json_path(&vcpkg_root_dir),
+            false,
+        );
+    }
+    return VcpkgBaseline::loads(&git::show::run(&vcpkg_root_dir, commit_hash), false);
 }
 
 pub fn from_index_file(port_name: &str, list_all: bool) -> Vec<String> {
@@ -36,12 +244,9 @@ pub fn from_index_file(port_name: &str, list_all: bool) -> Vec<String> {
     let vcpkg_manager = VcpkgManager::new(VcpkgArgs::load_or_default());
 
     for (registry, _url, _branch, vcpkg_root_dir) in vcpkg_manager.args.flatten_registry() {
-        match VcpkgSearchIndex::load(
-            &config::system_paths::DataPath::vcpkg_search_index_json(
-                vcpkg_manager.args.index_directory.as_ref().unwrap(),
-                &registry,
-            ),
-            false,
+        match VcpkgSearchIndex::load_index(
+            vcpkg_manager.args.index_directory.as_ref().unwrap(),
+            &registry,
         ) {
             None => return results,
             Some(index) => {
@@ -57,7 +262,7 @@ pub fn from_index_file(port_name: &str, list_all: bool) -> Vec<String> {
                 } else if port_name.ends_with("*") {
                     // prefix
                     let query = port_name.split_at(port_name.len() - 1).0;
-                    if let Some(mut data) = index.prefix_trie.get_data(&query, true) {
+                    if let Some(mut data) = index.prefix_trie().get_data(&query, true) {
                         data.sort();
                         for name in data {
                             if let Some(version) = index.baseline.default.get(name) {
@@ -68,7 +273,7 @@ pub fn from_index_file(port_name: &str, list_all: bool) -> Vec<String> {
                 } else if port_name.starts_with("*") {
                     // postfix
                     let query = util::str::reverse_string(port_name.split_at(1).1);
-                    if let Some(mut data) = index.postfix_trie.get_data(&query, true) {
+                    if let Some(mut data) = index.postfix_trie().get_data(&query, true) {
                         data.sort();
                         for name in data {
                             if let Some(version) = index.baseline.default.get(name) {
@@ -101,6 +306,253 @@ pub fn from_index_file(port_name: &str, list_all: bool) -> Vec<String> {
     return results;
 }
 
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PortSearchResult {
+    pub name: String,
+    pub version: String,
+    pub registry: String,
+    pub description: String,
+    pub homepage: String,
+    pub features: Vec<String>,
+    #[serde(skip)]
+    pub score: u32,
+}
+
+// ranked by name (exact, prefix, contains, typo), then feature names, description and homepage
+// wildcard queries (log*, *log, *log*) were matched on names only
+pub fn search(query: &str) -> Vec<PortSearchResult> {
+    let mut results = vec![];
+
+    let vcpkg_args = VcpkgArgs::load_or_default();
+    let query = query.trim().to_lowercase();
+    for (registry, _url, _branch, _vcpkg_root_dir) in vcpkg_args.flatten_registry() {
+        let index = match VcpkgSearchIndex::load_index(
+            vcpkg_args.index_directory.as_ref().unwrap(),
+            &registry,
+        ) {
+            None => continue,
+            Some(index) => index,
+        };
+
+        let empty = VcpkgPortSummary::default();
+        for (name, version) in &index.baseline.default {
+            let summary = index.summaries.get(name).unwrap_or(&empty);
+            let score = if query.contains('*') {
+                match_wildcard(&query, name)
+            } else {
+                score_port(&query, name, summary)
+            };
+            if score == 0 {
+                continue;
+            }
+            results.push(PortSearchResult {
+                name: name.clone(),
+                version: version.format_version_text(),
+                registry: registry.clone(),
+                description: summary.description.clone(),
+                homepage: summary.homepage.clone(),
+                features: summary.features.clone(),
+                score: score,
+            });
+        }
+    }
+
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+    return results;
+}
+
+fn match_wildcard(query: &str, name: &str) -> u32 {
+    let matched = match (query.strip_prefix('*'), query.strip_suffix('*')) {
+        (Some(q), Some(_)) => name.contains(q.trim_end_matches('*')),
+        (None, Some(q)) => name.starts_with(q),
+        (Some(q), None) => name.ends_with(q),
+        (None, None) => name == query,
+    };
+    return if matched { 1 } else { 0 };
+}
+
+static MAX_TYPO_DISTANCE: u32 = 2;
+
+fn score_port(query: &str, name: &str, summary: &VcpkgPortSummary) -> u32 {
+    if query.is_empty() {
+        return 0;
+    }
+
+    let length_penalty = name.len().abs_diff(query.len()).min(99) as u32;
+    if name == query {
+        return 1000;
+    }
+    if name.starts_with(query) {
+        return 900 - length_penalty;
+    }
+    if name.contains(query) {
+        return 700 - length_penalty;
+    }
+    // typo tolerance grows with query length, capped to rank above feature matches
+    let distance = util::str::edit_distance(query, name) as u32;
+    if distance <= (query.len() as u32 / 4).clamp(1, MAX_TYPO_DISTANCE) {
+        return 600 - distance * 100;
+    }
+
+    if summary.features.iter().any(|f| f == query) {
+        return 300;
+    }
+    if summary.features.iter().any(|f| f.contains(query)) {
+        return 250;
+    }
+    // every word of query appears in description
+    let description = summary.description.to_lowercase();
+    if query
+        .split_whitespace()
+        .all(|word| description.contains(word))
+    {
+        return 200;
+    }
+    if summary.homepage.to_lowercase().contains(query) {
+        return 100;
+    }
+    return 0;
+}
+
 fn format_port_version(registry: &str, name: &str, version: &VcpkgPortVersion) -> String {
     format!("[{registry}]  {}  {}", name, version.format_version_text())
 }
+
+// filesystem registry with fmt 11.0.2 in $TMP/<prefix>_<pid>/local
+#[cfg(test)]
+pub fn write_filesystem_registry(prefix: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("{prefix}_{}", std::process::id()))
+        .join("local");
+    std::fs::create_dir_all(dir.join("snapshots").join("fmt").join("11.0.2_0")).unwrap();
+    std::fs::create_dir_all(dir.join("versions").join("f-")).unwrap();
+    std::fs::write(
+        dir.join("versions").join("baseline.json"),
+        r#"{"default": {"fmt": {"baseline": "11.0.2", "port-version": 0}}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("versions").join("f-").join("fmt.json"),
+        r#"{"versions": [{"version": "11.0.2", "port-version": 0, "path": "$/snapshots/fmt/11.0.2_0"}]}"#,
+    )
+    .unwrap();
+    return dir;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_port() {
+        let summary = VcpkgPortSummary {
+            description: String::from("Fast C++ logging library."),
+            homepage: String::from("https://github.com/gabime/spdlog"),
+            features: vec![String::from("wchar")],
+        };
+        assert_eq!(score_port("spdlog", "spdlog", &summary), 1000);
+        assert!(score_port("spdlgo", "spdlog", &summary) > 0);
+        assert!(score_port("spd", "spdlog", &summary) > score_port("spdlgo", "spdlog", &summary));
+        assert_eq!(score_port("wchar", "spdlog", &summary), 300);
+        assert_eq!(score_port("logging library", "spdlog", &summary), 200);
+        assert_eq!(score_port("gabime", "spdlog", &summary), 100);
+        assert_eq!(score_port("openssl", "spdlog", &summary), 0);
+
+        // long queries tolerate at most MAX_TYPO_DISTANCE typos
+        let summary = VcpkgPortSummary::default();
+        let name = "abcdefghijklmnopqrstuvwxyz0123456789";
+        assert_eq!(
+            score_port("abcdefghijklmnopqrstuvwxyz012345678X", name, &summary),
+            500
+        );
+        assert_eq!(
+            score_port("abcdefghijklmnopqrstuvwxyz0123456XX9", name, &summary),
+            400
+        );
+        assert_eq!(
+            score_port("abcdefghijklmnopqrstuvwxyz0123XXX789", name, &summary),
+            0
+        );
+        assert_eq!(
+            score_port("abcdefghijklmnopqrstuvwxyzXXXXXXX789", name, &summary),
+            0
+        );
+
+        assert_eq!(match_wildcard("*log", "spdlog"), 1);
+        assert_eq!(match_wildcard("spd*", "spdlog"), 1);
+        assert_eq!(match_wildcard("*dlo*", "spdlog"), 1);
+        assert_eq!(match_wildcard("log*", "spdlog"), 0);
+    }
+
+    #[test]
+    fn test_select_registries() {
+        let registry = |name: &str, priority: i64, packages: &[&str]| {
+            (
+                name.to_string(),
+                priority,
+                packages
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<String>>(),
+            )
+        };
+
+        let candidates = vec![
+            registry("vcpkg", 0, &[]),
+            registry("private", 0, &[]),
+            registry("boost", 0, &["boost-*"]),
+            registry("asio", 0, &["boost-asio"]),
+        ];
+        assert_eq!(select_registries("boost-asio", &candidates), vec!["asio"]);
+        assert_eq!(select_registries("boost-beast", &candidates), vec!["boost"]);
+        assert_eq!(
+            select_registries("fmt", &candidates),
+            vec!["vcpkg", "private"]
+        );
+
+        let candidates = vec![
+            registry("vcpkg", 0, &[]),
+            registry("private", 10, &[]),
+            registry("boost", 0, &["boost-*"]),
+            registry("mirror", 1, &["boost-*"]),
+        ];
+        assert_eq!(select_registries("fmt", &candidates), vec!["private"]);
+        assert_eq!(
+            select_registries("boost-beast", &candidates),
+            vec!["mirror"]
+        );
+        assert!(select_registries("fmt", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_filesystem_registry() {
+        let dir = write_filesystem_registry("asc_filesystem_registry");
+        let port_dir = dir.join("snapshots").join("fmt").join("11.0.2_0");
+
+        let mut vcpkg_args = VcpkgArgs::default();
+        vcpkg_args.registry = vec![format!(
+            "{}?priority=1",
+            url::Url::from_file_path(&dir).unwrap()
+        )];
+        let (name, _url, _branch, vcpkg_root_dir) = vcpkg_args.get_registry("local");
+        assert_eq!(name, "local");
+        assert_eq!(vcpkg_root_dir, dir.to_string_lossy().replace(r"\", "/"));
+        assert!(vcpkg_args.is_filesystem_registry("local"));
+
+        let baseline = get_baseline(&vcpkg_args, "local", "").unwrap();
+        assert_eq!(baseline.default["fmt"].baseline, "11.0.2");
+
+        let versions = VcpkgPortVersions::load(
+            &config::system_paths::DataPath::vcpkg_versions_port_json_path(&vcpkg_root_dir, "fmt"),
+            false,
+        )
+        .unwrap();
+        assert!(versions.versions[0].git_tree.is_empty());
+        assert_eq!(
+            get_port_version_dir(&vcpkg_root_dir, &versions.versions[0].path),
+            port_dir.to_string_lossy().replace(r"\", "/")
+        );
+
+        std::fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+}
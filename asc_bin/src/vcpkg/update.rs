@@ -1,4 +1,4 @@
-use crate::{git, util};
+use crate::{cli::commands::VcpkgArgs, errors::ErrorTag, git, util};
 
 use super::VcpkgManager;
 
@@ -7,9 +7,19 @@ impl VcpkgManager {
         self.config_get(true);
 
         let mut result = true;
-        for (_name, url, branch, directory) in self.args.flatten_registry() {
-            // clone if not exists
-            if !util::fs::is_dir_exists(&directory) {
+        for (name, url, branch, directory) in self.args.flatten_registry() {
+            if VcpkgArgs::is_filesystem_url(&url) {
+                // filesystem registry was used in place
+                if !util::fs::is_dir_exists(&directory) {
+                    tracing::error!(
+                        registry = name,
+                        path = directory,
+                        error_tag = ErrorTag::DirectoryNotFoundError.as_ref(),
+                    );
+                    result = false;
+                }
+            } else if !util::fs::is_dir_exists(&directory) {
+                // clone if not exists
                 result &= git::clone::run(&url, &branch, &directory, &self.args.args);
             } else {
                 // fetch and reset
@@ -0,0 +1,375 @@
+use std::cmp::Ordering;
+
+static PORT_VERSION_DELIMITER: char = '#';
+static REQUIREMENT_DELIMITER: char = ',';
+
+// vcpkg version schemes with optional port version
+// version (1.2.3.4), version-semver (1.2.3-rc.1), version-date (2024-01-15.1)
+// version-string (vista) can only be matched exactly
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PortVersion {
+    pub text: String,
+    pub port_version: Option<u32>,
+    numbers: Vec<u64>,
+    pre_release: String,
+}
+
+impl PortVersion {
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let (base, port_version) = match text.split_once(PORT_VERSION_DELIMITER) {
+            None => (text, None),
+            Some((b, p)) => (b, p.parse::<u32>().ok()),
+        };
+
+        let mut version = Self {
+            text: base.to_string(),
+            port_version: port_version,
+            ..Default::default()
+        };
+
+        // version-date
+        let bytes = base.as_bytes();
+        let (core, pre_release) = if bytes.len() >= 10 && bytes[4] == b'-' && bytes[7] == b'-' {
+            (base.replacen('-', ".", 2), String::new())
+        } else {
+            let base = base.split_once('+').map(|(b, _)| b).unwrap_or(base);
+            match base.split_once('-') {
+                None => (base.to_string(), String::new()),
+                Some((c, p)) => (c.to_string(), p.to_string()),
+            }
+        };
+
+        let numbers = core
+            .trim_start_matches('v')
+            .split('.')
+            .map(|n| n.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>();
+        if let Some(numbers) = numbers {
+            version.numbers = numbers;
+            version.pre_release = pre_release;
+        }
+
+        return version;
+    }
+
+    pub fn is_comparable(&self) -> bool {
+        !self.numbers.is_empty()
+    }
+
+    fn cmp_numbers(left: &[u64], right: &[u64]) -> Ordering {
+        for i in 0..left.len().max(right.len()) {
+            let ordering = left.get(i).unwrap_or(&0).cmp(right.get(i).unwrap_or(&0));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        return Ordering::Equal;
+    }
+}
+
+impl PartialOrd for PortVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if !self.is_comparable() || !other.is_comparable() {
+            if self == other {
+                return Some(Ordering::Equal);
+            }
+            return None;
+        }
+
+        let ordering = Self::cmp_numbers(&self.numbers, &other.numbers)
+            // release was greater than pre release
+            .then_with(
+                || match (self.pre_release.is_empty(), other.pre_release.is_empty()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => self.pre_release.cmp(&other.pre_release),
+                },
+            )
+            .then_with(|| {
+                self.port_version
+                    .unwrap_or(0)
+                    .cmp(&other.port_version.unwrap_or(0))
+            });
+        return Some(ordering);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+    Wildcard,
+}
+
+// cargo like version requirement (^1.14, ~2.1, >=3.0,<4, *)
+// bare version was matched exactly to keep compatible with old asc.toml
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<(Op, PortVersion)>,
+}
+
+impl VersionReq {
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut comparators = vec![];
+        for part in text.split(REQUIREMENT_DELIMITER) {
+            let part = part.trim();
+            let (op, version) = if part == "*" {
+                (Op::Wildcard, "")
+            } else if let Some(v) = part.strip_prefix(">=") {
+                (Op::GreaterEq, v)
+            } else if let Some(v) = part.strip_prefix("<=") {
+                (Op::LessEq, v)
+            } else if let Some(v) = part.strip_prefix('>') {
+                (Op::Greater, v)
+            } else if let Some(v) = part.strip_prefix('<') {
+                (Op::Less, v)
+            } else if let Some(v) = part.strip_prefix('~') {
+                (Op::Tilde, v)
+            } else if let Some(v) = part.strip_prefix('^') {
+                (Op::Caret, v)
+            } else if let Some(v) = part.strip_prefix('=') {
+                (Op::Exact, v)
+            } else {
+                (Op::Exact, part)
+            };
+
+            let version = PortVersion::parse(version);
+            if op != Op::Wildcard && version.text.is_empty() {
+                return None;
+            }
+            // only exact match for version-string
+            if op != Op::Exact && op != Op::Wildcard && !version.is_comparable() {
+                return None;
+            }
+            comparators.push((op, version));
+        }
+        return Some(Self { comparators });
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.comparators.len() == 1 && self.comparators[0].0 == Op::Exact
+    }
+
+    // keep operator of single caret or tilde requirement (^1.14 -> ^2.0.1)
+    pub fn format_upgrade_text(&self, version: &PortVersion) -> String {
+        if self.comparators.len() == 1 {
+            match self.comparators[0].0 {
+                Op::Caret => return format!("^{}", version.text),
+                Op::Tilde => return format!("~{}", version.text),
+                _ => {}
+            }
+        }
+        match version.port_version {
+            None | Some(0) => version.text.clone(),
+            Some(p) => format!("{}{PORT_VERSION_DELIMITER}{p}", version.text),
+        }
+    }
+
+    pub fn matches(&self, version: &PortVersion) -> bool {
+        self.comparators
+            .iter()
+            .all(|(op, req)| Self::matches_comparator(*op, req, version))
+    }
+
+    // whether any version could match both requirements, bounds of comparators were intersected
+    pub fn intersects(&self, other: &VersionReq) -> bool {
+        // (version, inclusive)
+        let mut lower: Option<(PortVersion, bool)> = None;
+        let mut upper: Option<(PortVersion, bool)> = None;
+        for (op, req) in self.comparators.iter().chain(other.comparators.iter()) {
+            if *op == Op::Wildcard {
+                continue;
+            }
+            // version-string only intersects with itself
+            if !req.is_comparable() {
+                return self.matches(req) && other.matches(req);
+            }
+
+            let (l, u) = match op {
+                Op::Exact => (Some((req.clone(), true)), Some((req.clone(), true))),
+                Op::Greater => (Some((req.clone(), false)), None),
+                Op::GreaterEq => (Some((req.clone(), true)), None),
+                Op::Less => (None, Some((req.clone(), false))),
+                Op::LessEq => (None, Some((req.clone(), true))),
+                Op::Tilde | Op::Caret => {
+                    let bound = Self::upper_bound(*op, &req.numbers)
+                        .iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<String>>()
+                        .join(".");
+                    (
+                        Some((req.clone(), true)),
+                        Some((PortVersion::parse(&bound), false)),
+                    )
+                }
+                Op::Wildcard => unreachable!(),
+            };
+
+            if let Some((v, inclusive)) = l {
+                let tighter = match &lower {
+                    None => true,
+                    Some((c, c_inclusive)) => match v.partial_cmp(c) {
+                        Some(Ordering::Greater) => true,
+                        Some(Ordering::Equal) => *c_inclusive && !inclusive,
+                        _ => false,
+                    },
+                };
+                if tighter {
+                    lower = Some((v, inclusive));
+                }
+            }
+            if let Some((v, inclusive)) = u {
+                let tighter = match &upper {
+                    None => true,
+                    Some((c, c_inclusive)) => match v.partial_cmp(c) {
+                        Some(Ordering::Less) => true,
+                        Some(Ordering::Equal) => *c_inclusive && !inclusive,
+                        _ => false,
+                    },
+                };
+                if tighter {
+                    upper = Some((v, inclusive));
+                }
+            }
+        }
+
+        match (lower, upper) {
+            (Some((l, l_inclusive)), Some((u, u_inclusive))) => match l.partial_cmp(&u) {
+                Some(Ordering::Less) => true,
+                Some(Ordering::Equal) => l_inclusive && u_inclusive,
+                _ => false,
+            },
+            _ => true,
+        }
+    }
+
+    fn matches_comparator(op: Op, req: &PortVersion, version: &PortVersion) -> bool {
+        match op {
+            Op::Wildcard => return true,
+            Op::Exact => {
+                return req.text == version.text
+                    && req.port_version.unwrap_or(0) == version.port_version.unwrap_or(0);
+            }
+            _ => {}
+        }
+
+        if !version.is_comparable() {
+            return false;
+        }
+        let ordering = match version.partial_cmp(req) {
+            None => return false,
+            Some(o) => o,
+        };
+        match op {
+            Op::Greater => ordering == Ordering::Greater,
+            Op::GreaterEq => ordering != Ordering::Less,
+            Op::Less => ordering == Ordering::Less,
+            Op::LessEq => ordering != Ordering::Greater,
+            Op::Tilde | Op::Caret => {
+                if ordering == Ordering::Less {
+                    return false;
+                }
+                let upper = Self::upper_bound(op, &req.numbers);
+                PortVersion::cmp_numbers(&version.numbers, &upper) == Ordering::Less
+            }
+            Op::Exact | Op::Wildcard => unreachable!(),
+        }
+    }
+
+    // exclusive upper bound of ~ and ^
+    fn upper_bound(op: Op, numbers: &[u64]) -> Vec<u64> {
+        let index = match op {
+            Op::Tilde => numbers.len().min(2) - 1,
+            _ => numbers
+                .iter()
+                .position(|n| *n != 0)
+                .unwrap_or(numbers.len() - 1),
+        };
+        let mut upper = numbers[..=index].to_vec();
+        upper[index] += 1;
+        return upper;
+    }
+}
+
+// highest version which matches requirement
+pub fn select<'a>(requirement: &VersionReq, versions: &'a [String]) -> Option<&'a String> {
+    let mut selected: Option<(&String, PortVersion)> = None;
+    for text in versions {
+        let version = PortVersion::parse(text);
+        if !requirement.matches(&version) {
+            continue;
+        }
+        let greater = match &selected {
+            None => true,
+            Some((_, s)) => version.partial_cmp(s) == Some(Ordering::Greater),
+        };
+        if greater {
+            selected = Some((text, version));
+        }
+    }
+    return selected.map(|(text, _)| text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(requirement: &str, version: &str) -> bool {
+        VersionReq::parse(requirement)
+            .unwrap()
+            .matches(&PortVersion::parse(version))
+    }
+
+    #[test]
+    fn test_version_req() {
+        assert!(matches("11.0.2#1", "11.0.2#1"));
+        assert!(!matches("11.0.2", "11.0.2#1"));
+        assert!(matches("^1.14", "1.15.2#3"));
+        assert!(!matches("^1.14", "2.0.0"));
+        assert!(!matches("^0.4", "0.5.0"));
+        assert!(matches("~2.1", "2.1.9"));
+        assert!(!matches("~2.1", "2.2.0"));
+        assert!(matches(">=3.0,<4", "3.9.9"));
+        assert!(!matches(">=3.0,<4", "4.0.0"));
+        assert!(matches(">=2024-01-01", "2024-06-30.1"));
+        assert!(!matches(">=1.0.0", "1.0.0-rc.1"));
+        assert!(matches("vista", "vista"));
+        assert!(matches("*", "vista"));
+        assert!(VersionReq::parse("^vista").is_none());
+
+        let intersects = |left: &str, right: &str| {
+            VersionReq::parse(left)
+                .unwrap()
+                .intersects(&VersionReq::parse(right).unwrap())
+        };
+        assert!(intersects("^1.14", ">=1.15,<3"));
+        assert!(intersects("^1", "~1.2"));
+        assert!(intersects("*", "^2"));
+        assert!(intersects(">=2,<=3", ">=3"));
+        assert!(!intersects("^1", "^2"));
+        assert!(!intersects(">=2,<3", ">=3"));
+        assert!(!intersects("~2.1", "^2.2"));
+        assert!(intersects("vista", "*"));
+        assert!(!intersects("vista", "^1"));
+
+        let versions = vec![
+            String::from("2.0.0"),
+            String::from("1.15.2#1"),
+            String::from("1.15.2"),
+            String::from("1.14.0"),
+        ];
+        let req = VersionReq::parse("^1.14").unwrap();
+        assert_eq!(select(&req, &versions), Some(&versions[1]));
+        assert_eq!(
+            req.format_upgrade_text(&PortVersion::parse(&versions[0])),
+            "^2.0.0"
+        );
+    }
+}
@@ -1406,6 +1406,18 @@ typedef struct {
     unsigned int_data;
 } CXSourceLocation;
 
+/**
+ * Identifies a half-open character range in the source code.
+ *
+ * Use clang_getRangeStart() and clang_getRangeEnd() to retrieve the
+ * starting and end locations from a source range, respectively.
+ */
+typedef struct {
+    const void *ptr_data[2];
+    unsigned begin_int_data;
+    unsigned end_int_data;
+} CXSourceRange;
+
 /**
  * A character string.
  *
@@ -1782,4 +1794,60 @@ static const char *$clang_getCursorSemanticParent$ = "clang_getCursorSemanticPar
 typedef int (*func_ptr_clang_Cursor_isNull)(CXCursor cursor);
 static const char *$clang_Cursor_isNull$ = "clang_Cursor_isNull";
 
+/**
+ * Retrieve the physical extent of the source construct referenced by
+ * the given cursor.
+ *
+ * The extent of a cursor starts with the file/line/column pointing at the
+ * first character within the source construct that the cursor refers to and
+ * ends with the last character within that source construct. For a
+ * declaration, the extent covers the declaration itself. For a reference,
+ * the extent covers the location of the reference (e.g., where the referenced
+ * entity was actually used).
+ */
+// CINDEX_LINKAGE CXSourceRange clang_getCursorExtent(CXCursor);
+typedef CXSourceRange (*func_ptr_clang_getCursorExtent)(CXCursor);
+static const char *$clang_getCursorExtent$ = "clang_getCursorExtent";
+
+/**
+ * Retrieve a source location representing the first character within a
+ * source range.
+ */
+// CINDEX_LINKAGE CXSourceLocation clang_getRangeStart(CXSourceRange range);
+typedef CXSourceLocation (*func_ptr_clang_getRangeStart)(CXSourceRange range);
+static const char *$clang_getRangeStart$ = "clang_getRangeStart";
+
+/**
+ * Retrieve a source location representing the last character within a
+ * source range.
+ */
+// CINDEX_LINKAGE CXSourceLocation clang_getRangeEnd(CXSourceRange range);
+typedef CXSourceLocation (*func_ptr_clang_getRangeEnd)(CXSourceRange range);
+static const char *$clang_getRangeEnd$ = "clang_getRangeEnd";
+
+/**
+ * Returns the translation unit that a cursor originated from.
+ */
+// CINDEX_LINKAGE CXTranslationUnit clang_Cursor_getTranslationUnit(CXCursor);
+typedef CXTranslationUnit (*func_ptr_clang_Cursor_getTranslationUnit)(CXCursor);
+static const char *$clang_Cursor_getTranslationUnit$ = "clang_Cursor_getTranslationUnit";
+
+/**
+ * Retrieve the buffer associated with the given file.
+ *
+ * \param tu the translation unit
+ *
+ * \param file the file for which to retrieve the buffer.
+ *
+ * \param size [out] if non-NULL, will be set to the size of the buffer.
+ *
+ * \returns a pointer to the buffer in memory that holds the contents of
+ * \p file, or a NULL pointer when the file is not loaded.
+ */
+// CINDEX_LINKAGE const char *clang_getFileContents(CXTranslationUnit tu,
+//                                                  CXFile file, size_t *size);
+typedef const char *(*func_ptr_clang_getFileContents)(CXTranslationUnit tu,
+                                                      CXFile file, size_t *size);
+static const char *$clang_getFileContents$ = "clang_getFileContents";
+
 #endif // C_SOURCE_PARSER_FFI_CLANG_API
\ No newline at end of file
@@ -59,6 +59,11 @@ func_ptr_clang_getResultType clang_getResultType = NULL;
 func_ptr_clang_getTypeSpelling clang_getTypeSpelling = NULL;
 func_ptr_clang_getCursorSemanticParent clang_getCursorSemanticParent = NULL;
 func_ptr_clang_Cursor_isNull clang_Cursor_isNull = NULL;
+func_ptr_clang_getCursorExtent clang_getCursorExtent = NULL;
+func_ptr_clang_getRangeStart clang_getRangeStart = NULL;
+func_ptr_clang_getRangeEnd clang_getRangeEnd = NULL;
+func_ptr_clang_Cursor_getTranslationUnit clang_Cursor_getTranslationUnit = NULL;
+func_ptr_clang_getFileContents clang_getFileContents = NULL;
 
 
 static void replace_chars(IN_OUT char *str, IN const char old_char, IN const char new_char) {
@@ -90,6 +95,49 @@ static int starts_with(IN const char *str, IN const char *sub) {
 }
 
 
+// #include <...> or #include "...", the first delimiter in the directive decides
+static int is_angled_inclusion(IN CXCursor cursor) {
+    CXSourceRange extent = clang_getCursorExtent(cursor);
+    CXFile cx_file = NULL;
+    unsigned int begin = 0;
+    unsigned int end = 0;
+    clang_getFileLocation(clang_getRangeStart(extent), &cx_file, NULL, NULL, &begin);
+    clang_getFileLocation(clang_getRangeEnd(extent), NULL, NULL, NULL, &end);
+    if (NULL == cx_file) {
+        return FALSE;
+    }
+
+    size_t size = 0;
+    const char *contents = clang_getFileContents(clang_Cursor_getTranslationUnit(cursor), cx_file, &size);
+    if (NULL == contents) {
+        return FALSE;
+    }
+
+    for (size_t i = begin; i <= end && i < size; ++i) {
+        if (contents[i] == '<') {
+            return TRUE;
+        }
+        if (contents[i] == '"') {
+            return FALSE;
+        }
+    }
+    return FALSE;
+}
+
+
+static void store_third_party_inclusion(IN_OUT ClangParsedResult *result, IN const char *source_path, IN CXCursor cursor) {
+    CXString cx_str_include_name = clang_getCursorSpelling(cursor);
+    const char *include_name = clang_getCString(cx_str_include_name);
+    rust_btree_map_of_str_set_insert(result->third_party_include_by_sources, include_name, source_path);
+    if (TRUE == is_angled_inclusion(cursor)) {
+        rust_btree_map_of_str_set_insert(result->third_party_angled_include_by_sources, include_name, source_path);
+    }
+
+    // free clang resources
+    clang_disposeString(cx_str_include_name);
+}
+
+
 static char *get_namespaces(IN CXCursor cursor) {
     RustVecOfStr vec = rust_vec_of_str_new();
 
@@ -218,10 +266,16 @@ static enum CXChildVisitResult visit_symbols_and_inclusions(IN CXCursor cursor,
                 // collect inclusions
                 rust_btree_map_of_str_set_insert(result->header_include_by_sources, include_path, source_path);
                 rust_btree_map_of_str_set_insert(result->source_include_headers, source_path, include_path);
+            } else {
+                // collect third-party inclusions as spelled
+                store_third_party_inclusion(result, source_path, cursor);
             }
 
             // free clang resources
             clang_disposeString(cx_str_include_path);
+        } else {
+            // collect unresolved inclusions as spelled
+            store_third_party_inclusion(result, source_path, cursor);
         }
         break;
     }
@@ -433,6 +487,26 @@ AstCErrorCode load_library_clang(const char *library_clang_path) {
     if(INVALID_DYLIB_SYMBOL == clang_Cursor_isNull) {
         return AstCErrorSymbolClangCursorIsNullNotFound;
     }
+    clang_getCursorExtent = dylib_get(lib_clang, $clang_getCursorExtent$);
+    if(INVALID_DYLIB_SYMBOL == clang_getCursorExtent) {
+        return AstCErrorSymbolClangGetCursorExtentNotFound;
+    }
+    clang_getRangeStart = dylib_get(lib_clang, $clang_getRangeStart$);
+    if(INVALID_DYLIB_SYMBOL == clang_getRangeStart) {
+        return AstCErrorSymbolClangGetRangeStartNotFound;
+    }
+    clang_getRangeEnd = dylib_get(lib_clang, $clang_getRangeEnd$);
+    if(INVALID_DYLIB_SYMBOL == clang_getRangeEnd) {
+        return AstCErrorSymbolClangGetRangeEndNotFound;
+    }
+    clang_Cursor_getTranslationUnit = dylib_get(lib_clang, $clang_Cursor_getTranslationUnit$);
+    if(INVALID_DYLIB_SYMBOL == clang_Cursor_getTranslationUnit) {
+        return AstCErrorSymbolClangCursorGetTranslationUnitNotFound;
+    }
+    clang_getFileContents = dylib_get(lib_clang, $clang_getFileContents$);
+    if(INVALID_DYLIB_SYMBOL == clang_getFileContents) {
+        return AstCErrorSymbolClangGetFileContentsNotFound;
+    }
 
     return AstCErrorNone;
 }
@@ -454,6 +528,8 @@ ClangParsedResult scan_source_and_symbols(
     result.source_symbols = rust_btree_map_of_str_set_new();
     result.source_include_headers = rust_btree_map_of_str_set_new();
     result.header_include_by_sources = rust_btree_map_of_str_set_new();
+    result.third_party_include_by_sources = rust_btree_map_of_str_set_new();
+    result.third_party_angled_include_by_sources = rust_btree_map_of_str_set_new();
 
     const char *args[4] = {
         "-I",
@@ -40,6 +40,11 @@ typedef enum {
     AstCErrorSymbolClangGetTypeSpellingNotFound = 1020,
     AstCErrorSymbolClangGetCursorSemanticParentNotFound = 1021,
     AstCErrorSymbolClangCursorIsNullNotFound = 1022,
+    AstCErrorSymbolClangGetCursorExtentNotFound = 1023,
+    AstCErrorSymbolClangGetRangeStartNotFound = 1024,
+    AstCErrorSymbolClangGetRangeEndNotFound = 1025,
+    AstCErrorSymbolClangCursorGetTranslationUnitNotFound = 1026,
+    AstCErrorSymbolClangGetFileContentsNotFound = 1027,
 
     // call function errors
     AstCErrorSymbolClangCreateIndexCall = 5001,
@@ -64,6 +69,11 @@ typedef enum {
     AstCErrorSymbolClangGetTypeSpellingCall = 5020,
     AstCErrorSymbolClangGetCursorSemanticParentCall = 5021,
     AstCErrorSymbolClangCursorIsNullCall = 5022,
+    AstCErrorSymbolClangGetCursorExtentCall = 5023,
+    AstCErrorSymbolClangGetRangeStartCall = 5024,
+    AstCErrorSymbolClangGetRangeEndCall = 5025,
+    AstCErrorSymbolClangCursorGetTranslationUnitCall = 5026,
+    AstCErrorSymbolClangGetFileContentsCall = 5027,
 
     // unknown error
     AstCErrorUnknown = 65535,
@@ -80,6 +90,8 @@ typedef struct {
     RustBtreeMapOfStrSet source_symbols;
     RustBtreeMapOfStrSet source_include_headers;
     RustBtreeMapOfStrSet header_include_by_sources;
+    RustBtreeMapOfStrSet third_party_include_by_sources;
+    RustBtreeMapOfStrSet third_party_angled_include_by_sources;
 } ClangParsedResult;
 
 
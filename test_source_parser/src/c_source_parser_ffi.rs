@@ -26,6 +26,10 @@ pub struct ClangParsedResult {
     pub source_include_headers: RustBtreeMapOfStrSet,
     // Box::into_raw(Box::new(BTreeMap::<String, BTreeSet::<String>>::new()))
     pub header_include_by_sources: RustBtreeMapOfStrSet,
+    // Box::into_raw(Box::new(BTreeMap::<String, BTreeSet::<String>>::new()))
+    pub third_party_include_by_sources: RustBtreeMapOfStrSet,
+    // Box::into_raw(Box::new(BTreeMap::<String, BTreeSet::<String>>::new()))
+    pub third_party_angled_include_by_sources: RustBtreeMapOfStrSet,
 }
 
 extern "C" {
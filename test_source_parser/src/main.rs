@@ -110,6 +110,17 @@ impl SourceMappings {
                 result.header_include_by_sources as *mut BTreeMap<String, BTreeSet<String>>,
             )
         };
+        let _third_party_include_by_sources = unsafe {
+            Box::from_raw(
+                result.third_party_include_by_sources as *mut BTreeMap<String, BTreeSet<String>>,
+            )
+        };
+        let _third_party_angled_include_by_sources = unsafe {
+            Box::from_raw(
+                result.third_party_angled_include_by_sources
+                    as *mut BTreeMap<String, BTreeSet<String>>,
+            )
+        };
 
         // extend parsed results
         self.parsed_files.extend(current_parsed_files.into_iter());